@@ -0,0 +1,60 @@
+/* C interface of the bitmaps crate, built with the `ffi` feature. Matches the functions of the ffi module of the
+ * bitmaps crate, which this package builds into a static and a dynamic library. */
+
+#ifndef BITMAPS_H
+#define BITMAPS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle of a bitmap. Every handle returned by a function is owned by the caller and has to be released with
+ * bitmap_free. */
+typedef struct BitmapHandle BitmapHandle;
+
+/* Bytes exported by bitmap_to_bytes, to be released with bitmap_bytes_free. */
+typedef struct BitmapBytes {
+    uint8_t *data;
+    size_t len;
+} BitmapBytes;
+
+/* Creates an empty bitmap. */
+BitmapHandle *bitmap_new(void);
+
+/* Releases a bitmap. Passing a null pointer does nothing. */
+void bitmap_free(BitmapHandle *handle);
+
+/* Sets the presence of a value. */
+void bitmap_set(BitmapHandle *handle, uint32_t index);
+
+/* Clears the presence of a value. */
+void bitmap_clear(BitmapHandle *handle, uint32_t index);
+
+/* Returns whether a value is present. */
+bool bitmap_get(const BitmapHandle *handle, uint32_t index);
+
+/* Counts the values that are present. */
+uint64_t bitmap_count(const BitmapHandle *handle);
+
+/* Returns a new bitmap with the values present in either bitmap. Both handles may be the same. */
+BitmapHandle *bitmap_or(const BitmapHandle *left, const BitmapHandle *right);
+
+/* Serializes the bitmap into the little-endian binary format of SimpleBitmap::to_bytes. */
+BitmapBytes bitmap_to_bytes(const BitmapHandle *handle);
+
+/* Releases bytes exported by bitmap_to_bytes. */
+void bitmap_bytes_free(BitmapBytes bytes);
+
+/* Deserializes a bitmap from the binary format of SimpleBitmap::to_bytes. Returns a null pointer if the bytes are
+ * malformed. The data may only be null if len is 0. */
+BitmapHandle *bitmap_from_bytes(const uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
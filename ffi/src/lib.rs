@@ -0,0 +1,29 @@
+//! Static and dynamic library exporting the C interface of the `ffi` module of the [bitmaps] crate, which is declared
+//! in `include/bitmaps.h`.
+
+pub use bitmaps::ffi::*;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_declares_every_function_in_the_header() {
+        // given
+        let header = include_str!("../include/bitmaps.h");
+
+        // then
+        for function in [
+            "bitmap_new(",
+            "bitmap_free(",
+            "bitmap_set(",
+            "bitmap_clear(",
+            "bitmap_get(",
+            "bitmap_count(",
+            "bitmap_or(",
+            "bitmap_to_bytes(",
+            "bitmap_bytes_free(",
+            "bitmap_from_bytes(",
+        ] {
+            assert!(header.contains(function), "{function} is not declared");
+        }
+    }
+}
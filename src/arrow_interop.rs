@@ -0,0 +1,82 @@
+//! Conversions between [SimpleBitmap] and Arrow boolean arrays, so bitmaps can serve as validity or selection masks.
+
+use crate::SimpleBitmap;
+use arrow_array::{Array, BooleanArray};
+use arrow_buffer::{BooleanBuffer, Buffer};
+
+impl SimpleBitmap {
+    /// Returns a boolean array of the given length that is `true` exactly at the indices of the present values.
+    /// Values at or beyond the length are left out.
+    pub fn to_boolean_array(&self, len: usize) -> BooleanArray {
+        // Arrow stores the booleans as bits in least significant bit order, like the little-endian bytes of the u32s
+        let mut bytes: Vec<u8> = self
+            .words()
+            .iter()
+            .flat_map(|bucket| bucket.to_le_bytes())
+            .collect();
+        bytes.resize(len.div_ceil(8), 0);
+
+        // values beyond the length are cleared, so they don't linger in the buffer
+        if !len.is_multiple_of(8)
+            && let Some(last) = bytes.last_mut()
+        {
+            *last &= !(u8::MAX << (len % 8));
+        }
+
+        BooleanArray::new(BooleanBuffer::new(Buffer::from_vec(bytes), 0, len), None)
+    }
+
+    /// Creates a bitmap in which the indices of all `true` entries of the array are present. Null entries are treated
+    /// as absent.
+    pub fn from_boolean_array(array: &BooleanArray) -> Self {
+        let values = match array.nulls() {
+            Some(nulls) => array.values() & nulls.inner(),
+            None => array.values().clone(),
+        };
+
+        values.set_indices_u32().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_to_boolean_arrays_of_the_given_length() {
+        // given
+        let bm: SimpleBitmap = [0, 3, 9, 40].into_iter().collect();
+
+        // when
+        let array = bm.to_boolean_array(10);
+
+        // then
+        assert_eq!(array.len(), 10);
+        assert_eq!(array.null_count(), 0);
+        assert_eq!(
+            array.iter().map(Option::unwrap).collect::<Vec<bool>>(),
+            vec![
+                true, false, false, true, false, false, false, false, false, true
+            ]
+        );
+        assert_eq!(bm.to_boolean_array(100).true_count(), 4);
+    }
+
+    #[test]
+    fn it_converts_from_boolean_arrays_treating_nulls_as_absent() {
+        // given
+        let array = BooleanArray::from(vec![Some(true), None, Some(false), Some(true)]);
+
+        // when
+        let bm = SimpleBitmap::from_boolean_array(&array.slice(1, 3));
+
+        // then
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![2]);
+        assert_eq!(
+            SimpleBitmap::from_boolean_array(&array)
+                .iter()
+                .collect::<Vec<u32>>(),
+            vec![0, 3]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use crate::SimpleBitmap;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Bitmap that can be modified concurrently from many threads without locking, as all methods take `&self`.
+///
+/// Growing the u64s would require exclusive access, so the capacity is fixed on creation. Indices beyond it are
+/// rejected, see [AtomicBitmap::set].
+pub struct AtomicBitmap {
+    bits: Vec<AtomicU64>,
+}
+
+impl AtomicBitmap {
+    /// Creates an empty bitmap that can hold values up to `max_index`.
+    pub fn with_capacity(max_index: u32) -> Self {
+        Self {
+            bits: (0..=max_index / 64).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Returns the number of values the bitmap can hold.
+    pub fn capacity(&self) -> u64 {
+        self.bits.len() as u64 * 64
+    }
+
+    /// Sets the presence of a value and returns whether it was present before, so exactly one of several threads
+    /// setting the same index observes `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not below [AtomicBitmap::capacity].
+    pub fn set(&self, index: u32) -> bool {
+        let mask = 0b1 << (index & 0b111111);
+
+        self.bucket(index).fetch_or(mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Clears the presence of a value and returns whether it was present before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not below [AtomicBitmap::capacity].
+    pub fn clear(&self, index: u32) -> bool {
+        let mask = 0b1 << (index & 0b111111);
+
+        self.bucket(index).fetch_and(!mask, Ordering::AcqRel) & mask != 0
+    }
+
+    /// Returns whether a value is present. Indices beyond the capacity are never present.
+    pub fn get(&self, index: u32) -> bool {
+        self.bits
+            .get((index / 64) as usize)
+            .is_some_and(|bucket| (bucket.load(Ordering::Acquire) >> (index & 0b111111)) & 0b1 == 1)
+    }
+
+    /// Returns the number of present values.
+    ///
+    /// The u64s are read one after another, so concurrent modifications may or may not be reflected.
+    pub fn count_ones(&self) -> u64 {
+        self.bits
+            .iter()
+            .map(|bucket| u64::from(bucket.load(Ordering::Acquire).count_ones()))
+            .sum()
+    }
+
+    fn bucket(&self, index: u32) -> &AtomicU64 {
+        assert!(
+            u64::from(index) < self.capacity(),
+            "index {index} is out of range for a bitmap of {} values",
+            self.capacity()
+        );
+
+        &self.bits[(index / 64) as usize]
+    }
+}
+
+impl From<&AtomicBitmap> for SimpleBitmap {
+    /// Takes a snapshot of the bitmap. Like [AtomicBitmap::count_ones], it may or may not reflect concurrent
+    /// modifications.
+    fn from(bitmap: &AtomicBitmap) -> Self {
+        let words = bitmap
+            .bits
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Acquire))
+            .flat_map(|bucket| [bucket as u32, (bucket >> 32) as u32])
+            .collect();
+
+        SimpleBitmap::from_words(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+    use std::thread;
+
+    #[test]
+    fn it_sets_and_clears_bits() {
+        // given
+        let bm = AtomicBitmap::with_capacity(100);
+
+        // when
+        let first_set = bm.set(70);
+        let second_set = bm.set(70);
+        bm.set(3);
+        let cleared = bm.clear(3);
+
+        // then
+        assert_eq!(bm.capacity(), 128);
+        assert!(!first_set);
+        assert!(second_set);
+        assert!(cleared);
+        assert!(bm.get(70));
+        assert!(!bm.get(3));
+        assert!(!bm.get(1_000));
+        assert_eq!(bm.count_ones(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn it_rejects_indices_beyond_its_capacity() {
+        AtomicBitmap::with_capacity(63).set(64);
+    }
+
+    #[test]
+    fn it_sets_bits_from_many_threads() {
+        // given
+        let bm = AtomicBitmap::with_capacity(9_999);
+
+        // when
+        // every thread marks all indices, so each index is claimed by exactly one of them
+        let claimed: usize = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..10_000).filter(|index| !bm.set(*index)).count()))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum()
+        });
+
+        // then
+        assert_eq!(claimed, 10_000);
+        assert_eq!(bm.count_ones(), 10_000);
+        assert_eq!(SimpleBitmap::from(&bm).count_ones(), 10_000);
+    }
+}
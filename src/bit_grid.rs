@@ -0,0 +1,356 @@
+use crate::{Bitmap, SimpleBitmap};
+use alloc::vec;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// Two-dimensional grid of cells, e.g. an occupancy grid, stored row by row in a [SimpleBitmap].
+///
+/// The cell `(x, y)` is held by the value `y * width + x`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    bitmap: SimpleBitmap,
+    width: u32,
+    height: u32,
+}
+
+impl BitGrid {
+    /// Creates a grid of `width` columns and `height` rows without any cell set.
+    ///
+    /// Panics if the grid has more cells than a bitmap can hold.
+    pub fn new(width: u32, height: u32) -> Self {
+        assert!(
+            u64::from(width) * u64::from(height) <= u64::from(u32::MAX),
+            "a grid of {width} x {height} cells doesn't fit into a bitmap"
+        );
+
+        Self {
+            bitmap: SimpleBitmap::new(),
+            width,
+            height,
+        }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the set cells as values of a [SimpleBitmap], row by row.
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Sets the cell.
+    ///
+    /// Panics if the cell is outside of the grid.
+    pub fn set(&mut self, x: u32, y: u32) {
+        let index = self.index(x, y);
+        self.bitmap.set(index);
+    }
+
+    /// Clears the cell.
+    ///
+    /// Panics if the cell is outside of the grid.
+    pub fn clear(&mut self, x: u32, y: u32) {
+        let index = self.index(x, y);
+        self.bitmap.clear(index);
+    }
+
+    /// Returns whether the cell is set. Cells outside of the grid are never set.
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.bitmap.get(y * self.width + x)
+    }
+
+    /// Counts the set cells.
+    pub fn count_ones(&self) -> u64 {
+        self.bitmap.count_ones()
+    }
+
+    /// Returns the columns of the set cells in the row.
+    ///
+    /// Panics if the row is outside of the grid.
+    pub fn row(&self, y: u32) -> SimpleBitmap {
+        assert!(
+            y < self.height,
+            "row {y} is outside of a grid of {} rows",
+            self.height
+        );
+
+        let start = y * self.width;
+        self.bitmap.view(start..start + self.width).iter().collect()
+    }
+
+    /// Returns the rows of the set cells in the column.
+    ///
+    /// Panics if the column is outside of the grid.
+    pub fn column(&self, x: u32) -> SimpleBitmap {
+        assert!(
+            x < self.width,
+            "column {x} is outside of a grid of {} columns",
+            self.width
+        );
+
+        (0..self.height).filter(|y| self.get(x, *y)).collect()
+    }
+
+    /// Returns an iterator over the set cells as `(x, y)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.bitmap
+            .iter()
+            .map(|index| (index % self.width, index / self.width))
+    }
+
+    /// Returns the grid mirrored along its diagonal, so that the cell `(x, y)` becomes `(y, x)`. Columns of this grid
+    /// are the rows of the transposed one, which makes column-oriented queries as fast as row-oriented ones.
+    pub fn transpose(&self) -> BitGrid {
+        let words = self.bitmap.words();
+        let (width, height) = (u64::from(self.width), u64::from(self.height));
+        let mut transposed = vec![0; (width * height).div_ceil(32) as usize];
+
+        // the grid is transposed in blocks of 32 x 32 cells, each of which is transposed within 32 u32s
+        for block_y in (0..height).step_by(32) {
+            for block_x in (0..width).step_by(32) {
+                let rows = (height - block_y).min(32);
+                let columns = (width - block_x).min(32);
+
+                let mut block = [0; 32];
+                for (row, bucket) in block.iter_mut().enumerate().take(rows as usize) {
+                    *bucket = read_bits(
+                        words,
+                        (block_y + row as u64) * width + block_x,
+                        columns as u32,
+                    );
+                }
+
+                transpose_block(&mut block);
+
+                for (column, bucket) in block.iter().enumerate().take(columns as usize) {
+                    write_bits(
+                        &mut transposed,
+                        (block_x + column as u64) * height + block_y,
+                        *bucket,
+                    );
+                }
+            }
+        }
+
+        BitGrid {
+            bitmap: SimpleBitmap::from_words(transposed),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> u32 {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({x}, {y}) is outside of a grid of {} x {} cells",
+            self.width,
+            self.height
+        );
+
+        y * self.width + x
+    }
+
+    /// Combines the cells of two grids of the same dimensions.
+    fn combine(
+        self,
+        rhs: Self,
+        op: impl FnOnce(SimpleBitmap, SimpleBitmap) -> SimpleBitmap,
+    ) -> Self {
+        assert!(
+            (self.width, self.height) == (rhs.width, rhs.height),
+            "grids of {} x {} and {} x {} cells can't be combined",
+            self.width,
+            self.height,
+            rhs.width,
+            rhs.height
+        );
+
+        BitGrid {
+            bitmap: op(self.bitmap, rhs.bitmap),
+            ..self
+        }
+    }
+}
+
+/// Returns the `len` bits starting at the bit position `start` of the u32s, missing u32s are treated as zero.
+fn read_bits(words: &[u32], start: u64, len: u32) -> u32 {
+    let u32_index = (start / 32) as usize;
+    let bit_index_in_u32 = (start & 0b11111) as u32;
+
+    // the bits can span two u32s, of which the first provides the lower and the second the upper bits
+    let lower = words
+        .get(u32_index)
+        .map_or(0, |bucket| bucket >> bit_index_in_u32);
+    let upper = match words.get(u32_index + 1) {
+        Some(bucket) if bit_index_in_u32 != 0 => bucket << (32 - bit_index_in_u32),
+        _ => 0,
+    };
+
+    let mask = if len == 32 {
+        u32::MAX
+    } else {
+        !(u32::MAX << len)
+    };
+    (lower | upper) & mask
+}
+
+/// Sets the bits of `bits` starting at the bit position `start` of the u32s.
+fn write_bits(words: &mut [u32], start: u64, bits: u32) {
+    let u32_index = (start / 32) as usize;
+    let bit_index_in_u32 = (start & 0b11111) as u32;
+
+    words[u32_index] |= bits << bit_index_in_u32;
+    if bit_index_in_u32 != 0 && u32_index + 1 < words.len() {
+        words[u32_index + 1] |= bits >> (32 - bit_index_in_u32);
+    }
+}
+
+/// Transposes a matrix of 32 x 32 bits, in which bit `j` of u32 `i` holds the cell in row `i` and column `j`.
+///
+/// The quadrants of ever smaller blocks are swapped, starting with blocks of 16 x 16 bits, so that only five rounds of
+/// swaps over all u32s are needed.
+fn transpose_block(block: &mut [u32; 32]) {
+    let mut block_len = 16;
+    let mut mask: u32 = 0x0000ffff;
+
+    while block_len != 0 {
+        // visit the upper rows of each pair of blocks, i.e. the rows with the bit of the block length unset
+        let mut row = 0;
+        while row < 32 {
+            let swapped = ((block[row] >> block_len) ^ block[row + block_len]) & mask;
+            block[row + block_len] ^= swapped;
+            block[row] ^= swapped << block_len;
+
+            row = (row + block_len + 1) & !block_len;
+        }
+
+        block_len >>= 1;
+        mask ^= mask << block_len;
+    }
+}
+
+impl BitOr for BitGrid {
+    type Output = BitGrid;
+
+    /// Panics if the grids differ in their dimensions.
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, BitOr::bitor)
+    }
+}
+
+impl BitAnd for BitGrid {
+    type Output = BitGrid;
+
+    /// Panics if the grids differ in their dimensions.
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, BitAnd::bitand)
+    }
+}
+
+impl BitXor for BitGrid {
+    type Output = BitGrid;
+
+    /// Panics if the grids differ in their dimensions.
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, BitXor::bitxor)
+    }
+}
+
+impl Sub for BitGrid {
+    type Output = BitGrid;
+
+    /// Returns the cells set in `self` but not in `rhs`. Panics if the grids differ in their dimensions.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, Sub::sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_addresses_cells_by_coordinates() {
+        // given
+        let mut grid = BitGrid::new(40, 3);
+
+        // when
+        grid.set(0, 0);
+        grid.set(39, 0);
+        grid.set(39, 2);
+        grid.set(5, 1);
+        grid.clear(5, 1);
+
+        // then
+        assert!(grid.get(39, 2));
+        assert!(!grid.get(5, 1));
+        assert!(!grid.get(40, 0));
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(grid.row(0).iter().collect::<Vec<u32>>(), vec![0, 39]);
+        assert_eq!(grid.column(39).iter().collect::<Vec<u32>>(), vec![0, 2]);
+        assert_eq!(
+            grid.iter().collect::<Vec<(u32, u32)>>(),
+            vec![(0, 0), (39, 0), (39, 2)]
+        );
+    }
+
+    #[test]
+    fn it_combines_grids_cell_by_cell() {
+        // given
+        let mut left = BitGrid::new(4, 4);
+        left.set(1, 1);
+        left.set(2, 2);
+        let mut right = BitGrid::new(4, 4);
+        right.set(2, 2);
+        right.set(3, 3);
+
+        // then
+        assert_eq!((left.clone() | right.clone()).count_ones(), 3);
+        assert_eq!(
+            (left.clone() & right.clone())
+                .iter()
+                .collect::<Vec<(u32, u32)>>(),
+            vec![(2, 2)]
+        );
+        assert_eq!((left.clone() ^ right.clone()).count_ones(), 2);
+        assert_eq!(
+            (left - right).iter().collect::<Vec<(u32, u32)>>(),
+            vec![(1, 1)]
+        );
+    }
+
+    #[test]
+    fn it_transposes_grids_of_any_dimensions() {
+        // given
+        let mut grid = BitGrid::new(70, 45);
+        for y in 0..45 {
+            for x in (y % 5..70).step_by(3 + y as usize % 4) {
+                grid.set(x, y);
+            }
+        }
+
+        // when
+        let transposed = grid.transpose();
+
+        // then
+        assert_eq!((transposed.width(), transposed.height()), (45, 70));
+        assert_eq!(transposed.count_ones(), grid.count_ones());
+        for (x, y) in grid.iter() {
+            assert!(transposed.get(y, x));
+        }
+        assert_eq!(transposed.row(7), grid.column(7));
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of a grid")]
+    fn it_rejects_cells_outside_of_the_grid() {
+        BitGrid::new(4, 4).set(4, 0);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::SimpleBitmap;
+use alloc::vec::Vec;
+
+/// Builds a [SimpleBitmap] from indices in ascending order, e.g. for bulk loads from sorted files.
+///
+/// The u32 of the current index is kept aside and only written to the bitmap once an index of a later u32 is pushed, so
+/// every u32 is written exactly once.
+pub struct BitmapBuilder {
+    bits: Vec<u32>,
+    bucket: u32,
+    last: Option<u32>,
+}
+
+impl BitmapBuilder {
+    pub fn new() -> Self {
+        Self {
+            bits: Vec::new(),
+            bucket: 0,
+            last: None,
+        }
+    }
+
+    /// Creates a builder that can hold values up to `max_index` without reallocating.
+    pub fn with_capacity(max_index: u32) -> Self {
+        Self {
+            bits: Vec::with_capacity((max_index / 32) as usize + 1),
+            bucket: 0,
+            last: None,
+        }
+    }
+
+    /// Adds a value that is not lower than any value pushed before. Pushing the same value repeatedly is fine.
+    ///
+    /// Panics if the index is lower than the previously pushed one.
+    pub fn push_sorted(&mut self, index: u32) {
+        if let Some(last) = self.last {
+            assert!(
+                index >= last,
+                "index {index} is pushed after the higher index {last}"
+            );
+
+            // crossing into a later u32 commits the current one, skipped u32s are left empty
+            if index / 32 != last / 32 {
+                self.commit(last);
+            }
+        }
+
+        self.bucket |= 0b1 << (index & 0b11111);
+        self.last = Some(index);
+    }
+
+    /// Returns the bitmap of all pushed values.
+    pub fn build(mut self) -> SimpleBitmap {
+        if let Some(last) = self.last {
+            self.commit(last);
+        }
+
+        SimpleBitmap::from_words(self.bits)
+    }
+
+    fn commit(&mut self, last: u32) {
+        self.bits.resize((last / 32) as usize, 0);
+        self.bits.push(self.bucket);
+        self.bucket = 0;
+    }
+}
+
+impl Extend<u32> for BitmapBuilder {
+    /// Pushes all indices of the iterator via [BitmapBuilder::push_sorted].
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for index in iter {
+            self.push_sorted(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_bitmaps_from_sorted_indices() {
+        // given
+        let mut builder = BitmapBuilder::with_capacity(1_000);
+
+        // when
+        builder.extend([0, 3, 3, 31, 32, 200, 999]);
+        let bm = builder.build();
+
+        // then
+        assert_eq!(
+            bm,
+            [0, 3, 31, 32, 200, 999]
+                .into_iter()
+                .collect::<SimpleBitmap>()
+        );
+        assert_eq!(BitmapBuilder::new().build(), SimpleBitmap::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 is pushed after the higher index 5")]
+    fn it_panics_on_unsorted_indices() {
+        // given
+        let mut builder = BitmapBuilder::new();
+        builder.push_sorted(5);
+
+        // when
+        builder.push_sorted(4);
+    }
+}
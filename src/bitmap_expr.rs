@@ -0,0 +1,156 @@
+use crate::SimpleBitmap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitOr, Not, Sub};
+
+/// Boolean expression over borrowed bitmaps, which is evaluated in a single pass without materializing intermediate
+/// results.
+///
+/// Expressions are combined with `&`, `|`, `-` and `!`. Every u32 of the result is computed from the u32s at the same
+/// position of all bitmaps at once. Values beyond the u32 holding the highest present value of all bitmaps are absent
+/// from the result, so a negation only complements the values up to there.
+pub struct BitmapExpr<'a> {
+    node: Node<'a>,
+}
+
+enum Node<'a> {
+    Leaf(&'a [u32]),
+    And(Box<(Node<'a>, Node<'a>)>),
+    Or(Box<(Node<'a>, Node<'a>)>),
+    Difference(Box<(Node<'a>, Node<'a>)>),
+    Not(Box<Node<'a>>),
+}
+
+impl<'a> BitmapExpr<'a> {
+    /// Creates an expression of a single bitmap.
+    pub fn new(bitmap: &'a SimpleBitmap) -> Self {
+        Self {
+            node: Node::Leaf(bitmap.trimmed_bits()),
+        }
+    }
+
+    /// Evaluates the expression into a new bitmap.
+    pub fn evaluate(&self) -> SimpleBitmap {
+        let words: Vec<u32> = (0..self.node.len())
+            .map(|bucket_index| self.node.word(bucket_index))
+            .collect();
+
+        SimpleBitmap::from_words(words)
+    }
+
+    /// Counts the values present in the result of the expression, without materializing it.
+    pub fn count_ones(&self) -> u64 {
+        (0..self.node.len())
+            .map(|bucket_index| u64::from(self.node.word(bucket_index).count_ones()))
+            .sum()
+    }
+}
+
+impl Node<'_> {
+    /// Returns the number of u32s of the longest bitmap.
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(words) => words.len(),
+            Node::And(operands) | Node::Or(operands) | Node::Difference(operands) => {
+                operands.0.len().max(operands.1.len())
+            }
+            Node::Not(operand) => operand.len(),
+        }
+    }
+
+    /// Evaluates the u32 at the given position, bitmaps that are shorter contribute a zero u32.
+    fn word(&self, bucket_index: usize) -> u32 {
+        match self {
+            Node::Leaf(words) => words.get(bucket_index).copied().unwrap_or(0),
+            Node::And(operands) => operands.0.word(bucket_index) & operands.1.word(bucket_index),
+            Node::Or(operands) => operands.0.word(bucket_index) | operands.1.word(bucket_index),
+            Node::Difference(operands) => {
+                operands.0.word(bucket_index) & !operands.1.word(bucket_index)
+            }
+            Node::Not(operand) => !operand.word(bucket_index),
+        }
+    }
+}
+
+impl<'a> From<&'a SimpleBitmap> for BitmapExpr<'a> {
+    fn from(bitmap: &'a SimpleBitmap) -> Self {
+        BitmapExpr::new(bitmap)
+    }
+}
+
+impl<'a> BitAnd for BitmapExpr<'a> {
+    type Output = BitmapExpr<'a>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        BitmapExpr {
+            node: Node::And(Box::new((self.node, rhs.node))),
+        }
+    }
+}
+
+impl<'a> BitOr for BitmapExpr<'a> {
+    type Output = BitmapExpr<'a>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        BitmapExpr {
+            node: Node::Or(Box::new((self.node, rhs.node))),
+        }
+    }
+}
+
+impl<'a> Sub for BitmapExpr<'a> {
+    type Output = BitmapExpr<'a>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        BitmapExpr {
+            node: Node::Difference(Box::new((self.node, rhs.node))),
+        }
+    }
+}
+
+impl<'a> Not for BitmapExpr<'a> {
+    type Output = BitmapExpr<'a>;
+
+    fn not(self) -> Self::Output {
+        BitmapExpr {
+            node: Node::Not(Box::new(self.node)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+
+    #[test]
+    fn it_evaluates_expressions_like_the_operators_of_bitmaps() {
+        // given
+        let a: SimpleBitmap = (0..200).step_by(2).collect();
+        let b: SimpleBitmap = (0..100).step_by(3).collect();
+        let c: SimpleBitmap = [1, 5, 150, 300].into_iter().collect();
+
+        // when
+        let expr = (BitmapExpr::new(&a) & BitmapExpr::new(&b))
+            | (BitmapExpr::new(&c) - BitmapExpr::new(&a));
+
+        // then
+        let expected = (a.clone() & b.clone()) | (c.clone() - a.clone());
+        assert_eq!(expr.evaluate(), expected);
+        assert_eq!(expr.count_ones(), expected.count_ones());
+    }
+
+    #[test]
+    fn it_complements_values_up_to_the_highest_u32_of_all_bitmaps() {
+        // given
+        let a: SimpleBitmap = [1, 40].into_iter().collect();
+        let b: SimpleBitmap = [2].into_iter().collect();
+
+        // when
+        let expr = !BitmapExpr::new(&a) & BitmapExpr::new(&b).not().not();
+
+        // then
+        assert_eq!(expr.evaluate().iter().collect::<Vec<u32>>(), vec![2]);
+        assert_eq!((!BitmapExpr::new(&a)).count_ones(), 62);
+    }
+}
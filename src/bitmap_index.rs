@@ -0,0 +1,123 @@
+use crate::{Bitmap, SimpleBitmap};
+use alloc::collections::BTreeMap;
+
+/// Index mapping keys, like the values of a column, to bitmaps of the rows holding them, so that rows can be filtered
+/// by key.
+#[derive(Clone, Debug)]
+pub struct BitmapIndex<K> {
+    rows: BTreeMap<K, SimpleBitmap>,
+}
+
+impl<K: Ord> BitmapIndex<K> {
+    pub fn new() -> Self {
+        Self {
+            rows: BTreeMap::new(),
+        }
+    }
+
+    /// Records that the row holds the key.
+    pub fn insert(&mut self, key: K, row: u32) {
+        self.rows
+            .entry(key)
+            .or_insert_with(SimpleBitmap::new)
+            .set(row);
+    }
+
+    /// Removes the record that the row holds the key.
+    pub fn remove(&mut self, key: &K, row: u32) {
+        if let Some(rows) = self.rows.get_mut(key) {
+            rows.clear(row);
+
+            if rows.is_empty() {
+                self.rows.remove(key);
+            }
+        }
+    }
+
+    /// Returns the bitmap of the rows holding the key, if there are any.
+    pub fn get(&self, key: &K) -> Option<&SimpleBitmap> {
+        self.rows.get(key)
+    }
+
+    /// Returns an iterator over all keys held by at least one row, in ascending order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.rows.keys()
+    }
+
+    /// Returns the rows holding the key.
+    pub fn query_eq(&self, key: &K) -> SimpleBitmap {
+        self.rows
+            .get(key)
+            .cloned()
+            .unwrap_or_else(SimpleBitmap::new)
+    }
+
+    /// Returns the rows holding any of the keys.
+    pub fn query_in<'a>(&self, keys: impl IntoIterator<Item = &'a K>) -> SimpleBitmap
+    where
+        K: 'a,
+    {
+        SimpleBitmap::union_all(keys.into_iter().filter_map(|key| self.rows.get(key)))
+    }
+
+    /// Returns the rows below `universe` that don't hold the key, e.g. with the number of rows as universe.
+    pub fn query_not(&self, key: &K, universe: u32) -> SimpleBitmap {
+        match self.rows.get(key) {
+            Some(rows) => rows.complement(universe),
+            None => SimpleBitmap::new().complement(universe),
+        }
+    }
+}
+
+impl<K: Ord> FromIterator<(K, u32)> for BitmapIndex<K> {
+    fn from_iter<T: IntoIterator<Item = (K, u32)>>(iter: T) -> Self {
+        let mut index = BitmapIndex::new();
+        for (key, row) in iter {
+            index.insert(key, row);
+        }
+
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_filters_rows_by_key() {
+        // given
+        let regions = [
+            "speyside", "islay", "speyside", "highland", "islay", "speyside",
+        ];
+        let mut index: BitmapIndex<&str> = regions
+            .into_iter()
+            .enumerate()
+            .map(|(row, region)| (region, row as u32))
+            .collect();
+        index.insert("lowland", 6);
+        index.remove(&"lowland", 6);
+
+        // then
+        assert_eq!(
+            index.query_eq(&"speyside").iter().collect::<Vec<u32>>(),
+            vec![0, 2, 5]
+        );
+        assert_eq!(
+            index
+                .query_in(&["islay", "highland", "campbeltown"])
+                .iter()
+                .collect::<Vec<u32>>(),
+            vec![1, 3, 4]
+        );
+        assert_eq!(
+            index.query_not(&"speyside", 7).iter().collect::<Vec<u32>>(),
+            vec![1, 3, 4, 6]
+        );
+        assert_eq!(index.query_eq(&"lowland"), SimpleBitmap::new());
+        assert_eq!(
+            index.keys().collect::<Vec<&&str>>(),
+            vec![&"highland", &"islay", &"speyside"]
+        );
+    }
+}
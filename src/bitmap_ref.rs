@@ -0,0 +1,185 @@
+use crate::simple_bitmap::BinaryWords;
+use crate::{Ones, SimpleBitmap};
+use core::fmt::{Debug, Formatter};
+
+/// Borrowed, read-only view of the u32s of a bitmap, laid out like the ones of a [SimpleBitmap].
+///
+/// Queries run directly on the borrowed slice, so u32s received from elsewhere, e.g. a network buffer, don't have to be
+/// copied into a [SimpleBitmap] first.
+#[derive(Clone, Copy)]
+pub struct BitmapRef<'a> {
+    bits: &'a [u32],
+}
+
+impl<'a> BitmapRef<'a> {
+    /// Creates a view of the given u32s, where bit `i` of u32 `j` holds the value `j * 32 + i`.
+    pub fn new(bits: &'a [u32]) -> Self {
+        Self { bits }
+    }
+
+    /// Creates a view of the given bytes, which are reinterpreted as u32s in native byte order.
+    ///
+    /// Returns `None` if the bytes are not aligned to a u32 or their length is not a multiple of four.
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        // SAFETY: every bit pattern is a valid u32
+        let (prefix, bits, suffix) = unsafe { bytes.align_to::<u32>() };
+
+        (prefix.is_empty() && suffix.is_empty()).then_some(Self { bits })
+    }
+
+    /// Returns the borrowed u32s.
+    pub fn words(&self) -> &'a [u32] {
+        self.bits
+    }
+
+    /// Returns whether a value is present.
+    pub fn get(&self, index: u32) -> bool {
+        self.bits
+            .get((index / 32) as usize)
+            .is_some_and(|bucket| (bucket >> (index & 0b11111)) & 0b1 == 1)
+    }
+
+    /// Returns the number of present values.
+    pub fn count_ones(&self) -> u64 {
+        self.bits
+            .iter()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum()
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|bucket| *bucket == 0)
+    }
+
+    /// Returns the lowest index at which a value is present.
+    pub fn first_set(&self) -> Option<u32> {
+        self.bits
+            .iter()
+            .enumerate()
+            .find(|(_, bucket)| **bucket != 0)
+            .map(|(u32_index_in_bits_vec, bucket)| {
+                u32_index_in_bits_vec as u32 * 32 + bucket.trailing_zeros()
+            })
+    }
+
+    /// Returns the highest index at which a value is present.
+    pub fn last_set(&self) -> Option<u32> {
+        self.bits
+            .iter()
+            .enumerate()
+            .rfind(|(_, bucket)| **bucket != 0)
+            .map(|(u32_index_in_bits_vec, bucket)| {
+                u32_index_in_bits_vec as u32 * 32 + (31 - bucket.leading_zeros())
+            })
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'a> {
+        Ones::new(self.bits)
+    }
+}
+
+impl Debug for BitmapRef<'_> {
+    /// Shows summary statistics like [SimpleBitmap]. The alternate form `{:#?}` additionally dumps all u32s in binary.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("BitmapRef");
+        debug
+            .field("count", &self.count_ones())
+            .field("min", &self.first_set())
+            .field("max", &self.last_set())
+            .field("words", &self.bits.len());
+
+        if alternate {
+            debug.field("bits", &BinaryWords(self.bits));
+        }
+
+        debug.finish()
+    }
+}
+
+impl<'a> From<&'a SimpleBitmap> for BitmapRef<'a> {
+    fn from(bitmap: &'a SimpleBitmap) -> Self {
+        Self::new(bitmap.words())
+    }
+}
+
+impl From<BitmapRef<'_>> for SimpleBitmap {
+    /// Copies the borrowed u32s into an owned bitmap.
+    fn from(bitmap: BitmapRef<'_>) -> Self {
+        SimpleBitmap::from_words(bitmap.bits.to_vec())
+    }
+}
+
+impl<'a> IntoIterator for BitmapRef<'a> {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_queries_borrowed_u32s() {
+        // given
+        let words = [0b1001, 0, 0b1 << 31];
+
+        // when
+        let bm = BitmapRef::new(&words);
+
+        // then
+        assert!(bm.get(0));
+        assert!(bm.get(3));
+        assert!(bm.get(95));
+        assert!(!bm.get(96));
+        assert!(!bm.is_empty());
+        assert_eq!(bm.count_ones(), 3);
+        assert_eq!(bm.first_set(), Some(0));
+        assert_eq!(bm.last_set(), Some(95));
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![0, 3, 95]);
+        assert_eq!(
+            SimpleBitmap::from(bm),
+            [0, 3, 95].into_iter().collect::<SimpleBitmap>()
+        );
+    }
+
+    #[test]
+    fn it_reinterprets_aligned_bytes() {
+        // given
+        let words = [0b110_u32, 0b1];
+        let bytes: &[u8] = unsafe { words.align_to::<u8>().1 };
+
+        // then
+        assert_eq!(
+            BitmapRef::from_bytes(bytes)
+                .unwrap()
+                .iter()
+                .collect::<Vec<u32>>(),
+            vec![1, 2, 32]
+        );
+        assert!(BitmapRef::from_bytes(&bytes[1..5]).is_none());
+        assert!(BitmapRef::from_bytes(&bytes[..6]).is_none());
+    }
+
+    #[test]
+    fn it_summarizes_the_u32s_in_debug_output() {
+        // given
+        let bits = [0b10, 0, 0b1 << 8];
+
+        // when
+        let bm = BitmapRef::new(&bits);
+
+        // then
+        assert_eq!(
+            format!("{bm:?}"),
+            "BitmapRef { count: 2, min: Some(1), max: Some(72), words: 3 }"
+        );
+        assert!(format!("{bm:#?}").contains("00000000000000000000000100000000"));
+    }
+}
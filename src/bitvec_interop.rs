@@ -0,0 +1,107 @@
+//! Conversions between [SimpleBitmap] and the bit vectors of the [bitvec] crate. Bit `i` of a bit vector corresponds to
+//! the value `i` of a bitmap.
+
+use crate::SimpleBitmap;
+use bitvec::order::{BitOrder, Lsb0};
+use bitvec::slice::BitSlice;
+use bitvec::store::BitStore;
+use bitvec::vec::BitVec;
+
+impl<T: BitStore, O: BitOrder> From<&BitSlice<T, O>> for SimpleBitmap {
+    fn from(bits: &BitSlice<T, O>) -> Self {
+        bits.iter_ones()
+            .map(|index| u32::try_from(index).expect("bit index fits in a u32"))
+            .collect()
+    }
+}
+
+impl From<BitVec<u32, Lsb0>> for SimpleBitmap {
+    /// Takes over the u32s of the bit vector, which already have the layout of a bitmap once its bits start at the
+    /// first bit of the first u32.
+    fn from(mut bits: BitVec<u32, Lsb0>) -> Self {
+        // a bit vector created from a slice keeps the offset of the slice into its first u32
+        bits.force_align();
+        // bits of the u32s beyond the length of the bit vector are unspecified and have to be cleared
+        bits.set_uninitialized(false);
+
+        SimpleBitmap::from_words(bits.into_vec())
+    }
+}
+
+impl From<&SimpleBitmap> for BitVec<u32, Lsb0> {
+    /// Returns a bit vector that ends with the highest present value.
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        let mut bits = BitVec::from_slice(bitmap.words());
+        bits.truncate(
+            bitmap
+                .last_set()
+                .map_or(0, |last_set| last_set as usize + 1),
+        );
+
+        bits
+    }
+}
+
+impl From<SimpleBitmap> for BitVec<u32, Lsb0> {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        BitVec::from(&bitmap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitvec::order::Msb0;
+    use bitvec::view::BitView;
+    use bitvec::{bits, bitvec};
+
+    #[test]
+    fn it_converts_from_and_to_bit_vectors() {
+        // given
+        let bm: SimpleBitmap = [1, 2, 40].into_iter().collect();
+
+        // when
+        let bits = BitVec::<u32, Lsb0>::from(&bm);
+
+        // then
+        assert_eq!(bits.len(), 41);
+        assert_eq!(bits.iter_ones().collect::<Vec<usize>>(), vec![1, 2, 40]);
+        assert_eq!(SimpleBitmap::from(bits), bm);
+    }
+
+    #[test]
+    fn it_preserves_the_bit_order_of_any_bit_slice() {
+        // given
+        let msb_bits = bits![u8, Msb0; 1, 0, 0, 1, 1];
+        let mut truncated = bitvec![u32, Lsb0; 1; 40];
+        truncated.truncate(3);
+
+        // then
+        assert_eq!(
+            SimpleBitmap::from(msb_bits).iter().collect::<Vec<u32>>(),
+            vec![0, 3, 4]
+        );
+        assert_eq!(
+            SimpleBitmap::from(truncated).iter().collect::<Vec<u32>>(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn it_preserves_the_bit_order_of_offset_bit_vectors() {
+        // given
+        let offset = [0b1111_0000u32].view_bits::<Lsb0>()[4..8].to_bitvec();
+        let mut across_u32s = [u32::MAX << 28, 0b101].view_bits::<Lsb0>()[30..].to_bitvec();
+        across_u32s.truncate(5);
+
+        // then
+        assert_eq!(
+            SimpleBitmap::from(offset).iter().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            SimpleBitmap::from(across_u32s).iter().collect::<Vec<u32>>(),
+            vec![0, 1, 2, 4]
+        );
+    }
+}
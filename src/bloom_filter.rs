@@ -0,0 +1,165 @@
+use crate::{Bitmap, SimpleBitmap};
+use core::iter;
+
+/// Probabilistic set of byte strings that stores `hashes` bits per item in a [Bitmap] of `len` bits.
+///
+/// [BloomFilter::contains] never misses an inserted item, but may report items that were never inserted. The rate of
+/// these false positives grows with the number of items, see [BloomFilter::with_false_positive_rate] for picking the
+/// length and the number of hashes for an expected number of items.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomFilter<B: Bitmap = SimpleBitmap> {
+    bitmap: B,
+    len: u32,
+    hashes: u32,
+}
+
+impl<B: Bitmap + FromIterator<u32>> BloomFilter<B> {
+    /// Creates a filter of `len` bits that sets `hashes` bits per item.
+    ///
+    /// Panics if `len` or `hashes` is zero.
+    pub fn new(len: u32, hashes: u32) -> Self {
+        assert!(len > 0, "a bloom filter needs at least one bit");
+        assert!(hashes > 0, "a bloom filter needs at least one hash");
+
+        Self {
+            bitmap: iter::empty().collect(),
+            len,
+            hashes,
+        }
+    }
+
+    /// Creates a filter sized for `expected_items` items, so that the rate of false positives stays at about
+    /// `false_positive_rate` until that many items are inserted.
+    ///
+    /// Panics if the rate is not between 0 and 1.
+    #[cfg(feature = "std")]
+    pub fn with_false_positive_rate(expected_items: u64, false_positive_rate: f64) -> Self {
+        let len = optimal_len(expected_items, false_positive_rate);
+        Self::new(len, optimal_hashes(expected_items, len))
+    }
+}
+
+impl<B: Bitmap> BloomFilter<B> {
+    /// Returns the number of bits of the filter.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if no item has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+
+    /// Returns the number of bits set per item.
+    pub fn hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Returns the bitmap holding the set bits.
+    pub fn bitmap(&self) -> &B {
+        &self.bitmap
+    }
+
+    /// Inserts the item by setting all of its bits.
+    pub fn insert(&mut self, item: &[u8]) {
+        for index in self.indices(item) {
+            self.bitmap.set(index);
+        }
+    }
+
+    /// Returns `false` if the item was definitely never inserted, `true` if it probably was.
+    pub fn contains(&self, item: &[u8]) -> bool {
+        self.indices(item).all(|index| self.bitmap.get(index))
+    }
+
+    /// Returns the bits of the item. They are derived from two hashes as `h1 + i * h2`, which is as good as `hashes`
+    /// independent hash functions.
+    fn indices(&self, item: &[u8]) -> impl Iterator<Item = u32> + use<B> {
+        let hash = fnv1a(item);
+        let (first, second) = (hash, mix(hash) | 1);
+        let len = u64::from(self.len);
+
+        (0..u64::from(self.hashes))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % len) as u32)
+    }
+}
+
+/// Returns the number of bits that keeps the rate of false positives at `false_positive_rate` for `expected_items`
+/// items, capped at u32::MAX.
+///
+/// Panics if the rate is not between 0 and 1.
+#[cfg(feature = "std")]
+pub fn optimal_len(expected_items: u64, false_positive_rate: f64) -> u32 {
+    assert!(
+        false_positive_rate > 0.0 && false_positive_rate < 1.0,
+        "false positive rate {false_positive_rate} is not between 0 and 1"
+    );
+
+    let len = -(expected_items.max(1) as f64) * false_positive_rate.ln()
+        / (core::f64::consts::LN_2 * core::f64::consts::LN_2);
+    len.ceil().clamp(1.0, f64::from(u32::MAX)) as u32
+}
+
+/// Returns the number of hashes per item that minimizes the rate of false positives of a filter of `len` bits holding
+/// `expected_items` items.
+#[cfg(feature = "std")]
+pub fn optimal_hashes(expected_items: u64, len: u32) -> u32 {
+    let hashes = f64::from(len) / expected_items.max(1) as f64 * core::f64::consts::LN_2;
+    hashes.round().max(1.0) as u32
+}
+
+/// 64-bit FNV-1a hash of the bytes.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Finalizer of SplitMix64, which scrambles the bits of a value so that they look random, e.g. to derive a second
+/// hash from the first one.
+pub(crate) fn mix(hash: u64) -> u64 {
+    let hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RoaringBitmap;
+
+    #[test]
+    fn it_contains_all_inserted_items() {
+        // given
+        let mut filter: BloomFilter = BloomFilter::new(10_000, 7);
+
+        // when
+        for id in 0..1_000_u32 {
+            filter.insert(&id.to_le_bytes());
+        }
+
+        // then
+        assert!((0..1_000_u32).all(|id| filter.contains(&id.to_le_bytes())));
+        assert!(filter.bitmap().count_ones() <= 7_000);
+        assert!(!BloomFilter::<RoaringBitmap>::new(64, 3).contains(b"spey"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_keeps_the_false_positive_rate_for_the_expected_items() {
+        // given
+        let mut filter: BloomFilter = BloomFilter::with_false_positive_rate(1_000, 0.01);
+
+        // when
+        for id in 0..1_000_u32 {
+            filter.insert(&id.to_le_bytes());
+        }
+        let false_positives = (1_000..11_000_u32)
+            .filter(|id| filter.contains(&id.to_le_bytes()))
+            .count();
+
+        // then
+        assert_eq!((filter.len(), filter.hashes()), (9_586, 7));
+        assert!(false_positives < 200, "{false_positives} false positives");
+    }
+}
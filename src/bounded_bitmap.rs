@@ -0,0 +1,273 @@
+use crate::{Bitmap, Ones, SimpleBitmap, Zeros};
+use core::ops::{BitAnd, BitOr, BitXor, Not, Range, Sub};
+
+/// [Bitmap] of a fixed number of slots, e.g. the slots of a schedule, that can only hold values below its length.
+///
+/// In contrast to a [SimpleBitmap], the absent values are known as well, so the complement via [Not], the number of
+/// absent values and an iterator over all slots are well-defined.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoundedBitmap {
+    bitmap: SimpleBitmap,
+    len: u32,
+}
+
+impl BoundedBitmap {
+    /// Creates a bitmap of `len` slots, without any value present.
+    pub fn new(len: u32) -> Self {
+        Self {
+            bitmap: SimpleBitmap::with_capacity(len.saturating_sub(1)),
+            len,
+        }
+    }
+
+    /// Returns the number of slots, so values below it can be held.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+
+    /// Returns the values that are present as a [SimpleBitmap].
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Counts the slots without a value.
+    pub fn count_zeros(&self) -> u64 {
+        u64::from(self.len) - self.bitmap.count_ones()
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'_> {
+        self.bitmap.iter()
+    }
+
+    /// Returns an iterator over the indices of the absent values, in ascending order.
+    pub fn iter_zeros(&self) -> Zeros<'_> {
+        self.bitmap.iter_zeros(self.len)
+    }
+
+    /// Returns an iterator over all slots, yielding whether a value is present in each of them.
+    pub fn slots(&self) -> Slots<'_> {
+        Slots {
+            bitmap: &self.bitmap,
+            indices: 0..self.len,
+        }
+    }
+
+    fn check_index(&self, index: u32) {
+        assert!(
+            index < self.len,
+            "index {index} is out of range for a bitmap of {} values",
+            self.len
+        );
+    }
+}
+
+impl Bitmap for BoundedBitmap {
+    /// # Panics
+    ///
+    /// Panics if the index is not below [BoundedBitmap::len].
+    fn set(&mut self, index: u32) {
+        self.check_index(index);
+        self.bitmap.set(index);
+    }
+
+    /// Values at or above [BoundedBitmap::len] can't be present, so clearing them does nothing.
+    fn clear(&mut self, index: u32) {
+        self.bitmap.clear(index);
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bitmap.get(index)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bitmap.count_ones()
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.bitmap.is_subset(&other.bitmap)
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.bitmap.is_disjoint(&other.bitmap)
+    }
+
+    fn intersection_len(&self, other: &Self) -> u64 {
+        self.bitmap.intersection_len(&other.bitmap)
+    }
+
+    // like the operators, the bitmap grows to the length of other if it is longer
+    fn union_with(&mut self, other: &Self) {
+        self.bitmap |= &other.bitmap;
+        self.len = self.len.max(other.len);
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        self.bitmap &= &other.bitmap;
+        self.len = self.len.max(other.len);
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.bitmap.difference_with(&other.bitmap);
+        self.len = self.len.max(other.len);
+    }
+}
+
+// bitmaps of different lengths are combined into a bitmap of the larger length, which can hold the values of both
+
+impl BitOr for BoundedBitmap {
+    type Output = BoundedBitmap;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        BoundedBitmap {
+            bitmap: self.bitmap | rhs.bitmap,
+            len: self.len.max(rhs.len),
+        }
+    }
+}
+
+impl BitAnd for BoundedBitmap {
+    type Output = BoundedBitmap;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        BoundedBitmap {
+            bitmap: self.bitmap & rhs.bitmap,
+            len: self.len.max(rhs.len),
+        }
+    }
+}
+
+impl BitXor for BoundedBitmap {
+    type Output = BoundedBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        BoundedBitmap {
+            bitmap: self.bitmap ^ rhs.bitmap,
+            len: self.len.max(rhs.len),
+        }
+    }
+}
+
+impl Sub for BoundedBitmap {
+    type Output = BoundedBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        BoundedBitmap {
+            bitmap: self.bitmap - rhs.bitmap,
+            len: self.len.max(rhs.len),
+        }
+    }
+}
+
+impl Not for BoundedBitmap {
+    type Output = BoundedBitmap;
+
+    /// Returns the bitmap holding exactly the values below [BoundedBitmap::len] that are absent from `self`.
+    fn not(self) -> Self::Output {
+        BoundedBitmap {
+            bitmap: self.bitmap.complement(self.len),
+            len: self.len,
+        }
+    }
+}
+
+impl From<BoundedBitmap> for SimpleBitmap {
+    fn from(bitmap: BoundedBitmap) -> Self {
+        bitmap.bitmap
+    }
+}
+
+impl<'a> IntoIterator for &'a BoundedBitmap {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over all slots of a [BoundedBitmap], yielding whether a value is present in each of them.
+///
+/// Created by [BoundedBitmap::slots].
+#[derive(Clone)]
+pub struct Slots<'a> {
+    bitmap: &'a SimpleBitmap,
+    indices: Range<u32>,
+}
+
+impl Iterator for Slots<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|index| self.bitmap.get(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Slots<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.bitmap.get(index))
+    }
+}
+
+impl ExactSizeIterator for Slots<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_knows_the_absent_values_within_its_length() {
+        // given
+        let mut bm = BoundedBitmap::new(5);
+
+        // when
+        bm.set(1);
+        bm.set(4);
+
+        // then
+        assert_eq!(bm.count_ones(), 2);
+        assert_eq!(bm.count_zeros(), 3);
+        assert_eq!(bm.iter_zeros().collect::<Vec<u32>>(), vec![0, 2, 3]);
+        assert_eq!(
+            bm.slots().collect::<Vec<bool>>(),
+            vec![false, true, false, false, true]
+        );
+        assert_eq!((!bm.clone()).iter().collect::<Vec<u32>>(), vec![0, 2, 3]);
+        assert_eq!(!!bm.clone(), bm);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 5 is out of range for a bitmap of 5 values")]
+    fn it_rejects_indices_beyond_its_length() {
+        BoundedBitmap::new(5).set(5);
+    }
+
+    #[test]
+    fn it_ignores_clearing_indices_beyond_its_length() {
+        // given
+        let mut bm = BoundedBitmap::new(5);
+        bm.set(4);
+
+        // when
+        bm.clear(5);
+        bm.clear(u32::MAX);
+
+        // then
+        assert!(!bm.remove(5));
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![4]);
+    }
+}
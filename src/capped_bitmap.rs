@@ -0,0 +1,124 @@
+use crate::{Bitmap, IndexOutOfRange, Ones, SimpleBitmap};
+
+/// [Bitmap] that rejects values above a maximum index, e.g. to guard against untrusted indices.
+///
+/// A [SimpleBitmap] grows to hold any index it is given, so setting u32::MAX allocates 512MB. The storage of a capped
+/// bitmap never grows beyond the u32s needed for its maximum index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CappedBitmap {
+    bitmap: SimpleBitmap,
+    max_index: u32,
+}
+
+impl CappedBitmap {
+    /// Creates a bitmap that accepts values up to and including `max_index`, without any value present.
+    pub fn with_max(max_index: u32) -> Self {
+        Self {
+            bitmap: SimpleBitmap::new(),
+            max_index,
+        }
+    }
+
+    /// Returns the highest index that can be set.
+    pub fn max_index(&self) -> u32 {
+        self.max_index
+    }
+
+    /// Returns the values that are present as a [SimpleBitmap].
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Sets the presence of a value at the given index, unless the index is above [CappedBitmap::max_index].
+    pub fn try_set(&mut self, index: u32) -> Result<(), IndexOutOfRange> {
+        if index > self.max_index {
+            return Err(IndexOutOfRange {
+                index,
+                max_index: self.max_index,
+            });
+        }
+
+        self.bitmap.set(index);
+        Ok(())
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'_> {
+        self.bitmap.iter()
+    }
+}
+
+impl Bitmap for CappedBitmap {
+    /// # Panics
+    ///
+    /// Panics if the index is above [CappedBitmap::max_index], use [CappedBitmap::try_set] to handle it instead.
+    fn set(&mut self, index: u32) {
+        if let Err(error) = self.try_set(index) {
+            panic!("{error}");
+        }
+    }
+
+    fn clear(&mut self, index: u32) {
+        self.bitmap.clear(index);
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bitmap.get(index)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bitmap.count_ones()
+    }
+}
+
+impl From<CappedBitmap> for SimpleBitmap {
+    fn from(bitmap: CappedBitmap) -> Self {
+        bitmap.bitmap
+    }
+}
+
+impl<'a> IntoIterator for &'a CappedBitmap {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_rejects_indices_above_the_maximum() {
+        // given
+        let mut bm = CappedBitmap::with_max(1_000);
+
+        // when
+        let accepted = bm.try_set(1_000);
+        let rejected = bm.try_set(u32::MAX);
+
+        // then
+        assert_eq!(accepted, Ok(()));
+        assert_eq!(
+            rejected,
+            Err(IndexOutOfRange {
+                index: u32::MAX,
+                max_index: 1_000
+            })
+        );
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![1_000]);
+        assert_eq!(bm.bitmap().memory_usage().words, 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 1001 is above the maximum index 1000 of the bitmap")]
+    fn it_panics_when_setting_indices_above_the_maximum() {
+        CappedBitmap::with_max(1_000).set(1_001);
+    }
+}
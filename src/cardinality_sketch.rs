@@ -0,0 +1,189 @@
+use crate::SimpleBitmap;
+use crate::bloom_filter::mix;
+use crate::error::{ByteReader, DecodeError};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// HyperLogLog sketch that estimates the number of distinct values inserted into it, e.g. of a [SimpleBitmap] via
+/// [SimpleBitmap::to_sketch].
+///
+/// A sketch takes 2^`precision` bytes independent of the number of values, and the sketches of several bitmaps can be
+/// merged to estimate the size of their union. The standard error of the estimate is about `1.04 / sqrt(2^precision)`,
+/// i.e. 1.6% for a precision of 12.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardinalitySketch {
+    // the highest rank seen per register, the register being selected by the upper bits of a value's hash
+    registers: Vec<u8>,
+    precision: u8,
+}
+
+impl CardinalitySketch {
+    /// Creates an empty sketch of 2^`precision` registers.
+    ///
+    /// Panics if the precision is not between 4 and 16.
+    pub fn new(precision: u8) -> Self {
+        assert!(
+            (4..=16).contains(&precision),
+            "precision {precision} is not between 4 and 16"
+        );
+
+        Self {
+            registers: vec![0; 1 << precision],
+            precision,
+        }
+    }
+
+    /// Returns the precision the sketch was created with.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Adds a value to the sketch.
+    pub fn insert(&mut self, value: u32) {
+        let hash = mix(u64::from(value));
+        let register = (hash >> (64 - self.precision)) as usize;
+
+        // the rank is the position of the first set bit after the bits selecting the register, the set bit below them
+        // limits it in case all of them are zero
+        let remaining = (hash << self.precision) | (0b1 << (self.precision - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+
+        self.registers[register] = self.registers[register].max(rank);
+    }
+
+    /// Adds all values inserted into `other` to this sketch, so it estimates the size of the union afterwards.
+    ///
+    /// Panics if the sketches differ in their precision.
+    pub fn merge(&mut self, other: &CardinalitySketch) {
+        assert_eq!(
+            self.precision, other.precision,
+            "sketches of different precision can't be merged"
+        );
+
+        for (register, other_register) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other_register);
+        }
+    }
+
+    /// Estimates the number of distinct values inserted into the sketch and all sketches merged into it.
+    #[cfg(feature = "std")]
+    pub fn estimate(&self) -> f64 {
+        let len = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / len),
+        };
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|rank| 1.0 / (1_u64 << rank) as f64)
+            .sum();
+        let estimate = alpha * len * len / sum;
+
+        // small cardinalities leave registers empty, for which counting the empty registers is more accurate
+        let empty_registers = self.registers.iter().filter(|rank| **rank == 0).count();
+        if estimate <= 2.5 * len && empty_registers > 0 {
+            len * (len / empty_registers as f64).ln()
+        } else {
+            estimate
+        }
+    }
+
+    /// Serializes the sketch into its precision followed by one byte per register.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.registers.len());
+        bytes.push(self.precision);
+        bytes.extend(&self.registers);
+
+        bytes
+    }
+
+    /// Deserializes a sketch from the format written by [CardinalitySketch::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = ByteReader::new(bytes);
+
+        let precision = reader.take(1)?[0];
+        if !(4..=16).contains(&precision) {
+            return Err(DecodeError::Invalid("precision is not between 4 and 16"));
+        }
+
+        let registers = reader.take(1 << precision)?.to_vec();
+        reader.finish()?;
+
+        if registers.iter().any(|rank| *rank > 65 - precision) {
+            return Err(DecodeError::Invalid("rank exceeds the bits of the hash"));
+        }
+
+        Ok(Self {
+            registers,
+            precision,
+        })
+    }
+}
+
+impl SimpleBitmap {
+    /// Builds a [CardinalitySketch] of the given precision holding all present values.
+    ///
+    /// Panics if the precision is not between 4 and 16.
+    pub fn to_sketch(&self, precision: u8) -> CardinalitySketch {
+        let mut sketch = CardinalitySketch::new(precision);
+        for index in self.iter() {
+            sketch.insert(index);
+        }
+
+        sketch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_sketches_through_bytes() {
+        // given
+        let bm: SimpleBitmap = (0..1_000).collect();
+        let sketch = bm.to_sketch(8);
+
+        // when
+        let bytes = sketch.to_bytes();
+
+        // then
+        assert_eq!(bytes.len(), 257);
+        assert_eq!(CardinalitySketch::from_bytes(&bytes), Ok(sketch));
+        assert_eq!(
+            CardinalitySketch::from_bytes(&bytes[..100]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_estimates_the_size_of_unions_across_shards() {
+        // given
+        let first_shard: SimpleBitmap = (0..60_000).collect();
+        let second_shard: SimpleBitmap = (40_000..100_000).step_by(2).collect();
+
+        // when
+        let mut sketch = first_shard.to_sketch(12);
+        sketch.merge(&second_shard.to_sketch(12));
+
+        // then
+        let union_len = first_shard.union_len(&second_shard) as f64;
+        assert!((sketch.estimate() - union_len).abs() / union_len < 0.05);
+        assert!((first_shard.to_sketch(12).estimate() - 60_000.0).abs() < 3_000.0);
+        assert!(
+            ([3, 7, 11]
+                .into_iter()
+                .collect::<SimpleBitmap>()
+                .to_sketch(12)
+                .estimate()
+                - 3.0)
+                .abs()
+                < 0.5
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Processing of bitmaps in fixed-size blocks of u32s, so that only one block per bitmap has to be in memory at a
+//! time. Combined with a file-backed source like `MmapBitmap`, this allows operating on bitmaps larger than memory.
+
+use crate::{BitmapRef, SimpleBitmap};
+use alloc::vec;
+
+/// Bitmap whose u32s can be read block by block.
+pub trait ChunkSource {
+    /// Returns the number of u32s of the bitmap.
+    fn word_len(&self) -> usize;
+
+    /// Fills the buffer with the u32s starting at `start`. u32s beyond the end of the bitmap are zero.
+    fn read_words(&self, start: usize, buffer: &mut [u32]);
+}
+
+/// Calls `f` with consecutive blocks of at most `chunk_len` u32s of the bitmap, together with the position of the
+/// first u32 of a block. Only the last block can be shorter.
+///
+/// Panics if `chunk_len` is zero.
+pub fn for_each_chunk(
+    bitmap: &impl ChunkSource,
+    chunk_len: usize,
+    mut f: impl FnMut(usize, &[u32]),
+) {
+    assert!(chunk_len > 0, "chunks have to hold at least one u32");
+
+    let len = bitmap.word_len();
+    let mut buffer = vec![0; chunk_len.min(len)];
+
+    for start in (0..len).step_by(chunk_len) {
+        let chunk = &mut buffer[..chunk_len.min(len - start)];
+        bitmap.read_words(start, chunk);
+        f(start, chunk);
+    }
+}
+
+/// Calls `f` with consecutive blocks of at most `chunk_len` u32s at the same positions of both bitmaps, together with
+/// the position of the first u32 of the blocks. The shorter bitmap is padded with zero u32s.
+///
+/// Panics if `chunk_len` is zero.
+pub fn op_chunks(
+    a: &impl ChunkSource,
+    b: &impl ChunkSource,
+    chunk_len: usize,
+    mut f: impl FnMut(usize, &[u32], &[u32]),
+) {
+    assert!(chunk_len > 0, "chunks have to hold at least one u32");
+
+    let len = a.word_len().max(b.word_len());
+    let mut a_buffer = vec![0; chunk_len.min(len)];
+    let mut b_buffer = vec![0; chunk_len.min(len)];
+
+    for start in (0..len).step_by(chunk_len) {
+        let chunk_len = chunk_len.min(len - start);
+        let (a_chunk, b_chunk) = (&mut a_buffer[..chunk_len], &mut b_buffer[..chunk_len]);
+        a.read_words(start, a_chunk);
+        b.read_words(start, b_chunk);
+        f(start, a_chunk, b_chunk);
+    }
+}
+
+/// Copies the u32s of a slice starting at `start` into the buffer and zeroes the rest.
+fn read_slice(words: &[u32], start: usize, buffer: &mut [u32]) {
+    let available = words.get(start..).unwrap_or_default();
+    let copied = available.len().min(buffer.len());
+
+    buffer[..copied].copy_from_slice(&available[..copied]);
+    buffer[copied..].fill(0);
+}
+
+impl ChunkSource for SimpleBitmap {
+    fn word_len(&self) -> usize {
+        self.words().len()
+    }
+
+    fn read_words(&self, start: usize, buffer: &mut [u32]) {
+        read_slice(self.words(), start, buffer);
+    }
+}
+
+impl ChunkSource for BitmapRef<'_> {
+    fn word_len(&self) -> usize {
+        self.words().len()
+    }
+
+    fn read_words(&self, start: usize, buffer: &mut [u32]) {
+        read_slice(self.words(), start, buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_visits_all_u32s_in_blocks() {
+        // given
+        let bm: SimpleBitmap = (0..1_000).step_by(7).collect();
+
+        // when
+        let mut starts = Vec::new();
+        let mut words = Vec::new();
+        for_each_chunk(&bm, 10, |start, chunk| {
+            starts.push(start);
+            words.extend_from_slice(chunk);
+        });
+
+        // then
+        assert_eq!(starts, vec![0, 10, 20, 30]);
+        assert_eq!(SimpleBitmap::from_words(words), bm);
+    }
+
+    #[test]
+    fn it_combines_blocks_of_two_bitmaps() {
+        // given
+        let left: SimpleBitmap = (0..1_000).step_by(3).collect();
+        let right: SimpleBitmap = (500..3_000).step_by(5).collect();
+        let right_words = right.words().to_vec();
+
+        // when
+        let mut union = Vec::new();
+        op_chunks(&left, &BitmapRef::new(&right_words), 16, |_, a, b| {
+            union.extend(a.iter().zip(b).map(|(a, b)| a | b));
+        });
+
+        // then
+        assert_eq!(SimpleBitmap::from_words(union), left | right);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{Bitmap, SimpleBitmap};
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+/// Bitmap of small counters, e.g. for counting bloom filters or reference counts, that packs a counter of `BITS` bits
+/// per index into u32s. `BITS` is 2, 4 or 8, so no counter spans two u32s.
+///
+/// Counters saturate instead of overflowing: incrementing a counter at [CountingBitmap::MAX] keeps it there, and
+/// decrementing a counter at zero keeps it at zero.
+#[derive(Clone, Debug)]
+pub struct CountingBitmap<const BITS: u32> {
+    bits: Vec<u32>,
+}
+
+impl<const BITS: u32> CountingBitmap<BITS> {
+    /// Highest value a counter can hold.
+    pub const MAX: u32 = (1 << BITS) - 1;
+
+    /// Number of counters in each u32.
+    const COUNTERS_PER_U32: u32 = 32 / BITS;
+
+    pub fn new() -> Self {
+        const { assert!(matches!(BITS, 2 | 4 | 8), "counters have 2, 4 or 8 bits") };
+
+        Self { bits: Vec::new() }
+    }
+
+    /// Returns the counter at the given index.
+    pub fn get(&self, index: u32) -> u32 {
+        let (u32_index_in_bits_vec, shift) = Self::position(index);
+
+        self.bits
+            .get(u32_index_in_bits_vec)
+            .map_or(0, |bucket| (bucket >> shift) & Self::MAX)
+    }
+
+    /// Sets the counter at the given index, saturating at [CountingBitmap::MAX].
+    pub fn set(&mut self, index: u32, count: u32) {
+        let (u32_index_in_bits_vec, shift) = Self::position(index);
+
+        // counters beyond the end of the bits vec are zero, so the vec only has to be extended for non-zero ones
+        if u32_index_in_bits_vec >= self.bits.len() {
+            if count == 0 {
+                return;
+            }
+            self.bits.resize(u32_index_in_bits_vec + 1, 0);
+        }
+
+        let bucket = &mut self.bits[u32_index_in_bits_vec];
+        *bucket = (*bucket & !(Self::MAX << shift)) | (count.min(Self::MAX) << shift);
+    }
+
+    /// Increments the counter at the given index unless it is saturated and returns its new value.
+    pub fn increment(&mut self, index: u32) -> u32 {
+        let count = (self.get(index) + 1).min(Self::MAX);
+        self.set(index, count);
+
+        count
+    }
+
+    /// Decrements the counter at the given index unless it is zero and returns its new value.
+    pub fn decrement(&mut self, index: u32) -> u32 {
+        let count = self.get(index).saturating_sub(1);
+        self.set(index, count);
+
+        count
+    }
+
+    /// Returns `true` if the counter at the given index is [CountingBitmap::MAX], so it doesn't count any further.
+    pub fn is_saturated(&self, index: u32) -> bool {
+        self.get(index) == Self::MAX
+    }
+
+    /// Returns the bitmap of the indices with a non-zero counter.
+    pub fn to_bitmap(&self) -> SimpleBitmap {
+        let mut bitmap = SimpleBitmap::new();
+
+        // u32s without any counter set are skipped as a whole
+        for (u32_index_in_bits_vec, bucket) in self.bits.iter().enumerate() {
+            if *bucket == 0 {
+                continue;
+            }
+
+            let first_index = u32_index_in_bits_vec as u32 * Self::COUNTERS_PER_U32;
+            for counter in 0..Self::COUNTERS_PER_U32 {
+                if (bucket >> (counter * BITS)) & Self::MAX != 0 {
+                    bitmap.set(first_index + counter);
+                }
+            }
+        }
+
+        bitmap
+    }
+
+    /// Returns the bits vec without trailing zero u32s, which decrementing counters to zero leaves behind.
+    fn trimmed_bits(&self) -> &[u32] {
+        let len = self
+            .bits
+            .iter()
+            .rposition(|bucket| *bucket != 0)
+            .map_or(0, |last_non_zero| last_non_zero + 1);
+
+        &self.bits[..len]
+    }
+
+    /// Returns the index of the u32 holding the counter and the position of its lowest bit within the u32.
+    fn position(index: u32) -> (usize, u32) {
+        (
+            (index / Self::COUNTERS_PER_U32) as usize,
+            (index % Self::COUNTERS_PER_U32) * BITS,
+        )
+    }
+}
+
+impl<const BITS: u32> PartialEq for CountingBitmap<BITS> {
+    fn eq(&self, other: &Self) -> bool {
+        // counters with the same values are equal, no matter how many trailing zero u32s they carry
+        self.trimmed_bits() == other.trimmed_bits()
+    }
+}
+
+impl<const BITS: u32> Eq for CountingBitmap<BITS> {}
+
+impl<const BITS: u32> Hash for CountingBitmap<BITS> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // trailing zero u32s are skipped, so that equal counters always produce equal hashes
+        self.trimmed_bits().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_counts_up_and_down_per_index() {
+        // given
+        let mut counters = CountingBitmap::<4>::new();
+
+        // when
+        counters.increment(3);
+        counters.increment(3);
+        counters.increment(1_000);
+        counters.decrement(1_000);
+        counters.decrement(5);
+
+        // then
+        assert_eq!(counters.get(3), 2);
+        assert_eq!(counters.get(1_000), 0);
+        assert_eq!(counters.get(5), 0);
+        assert_eq!(counters.get(4), 0);
+    }
+
+    #[test]
+    fn it_saturates_counters() {
+        // given
+        let mut counters = CountingBitmap::<2>::new();
+
+        // when
+        for _ in 0..5 {
+            counters.increment(17);
+        }
+
+        // then
+        assert_eq!(counters.get(17), 3);
+        assert!(counters.is_saturated(17));
+        assert_eq!(counters.get(16), 0);
+        assert_eq!(counters.get(18), 0);
+    }
+
+    #[test]
+    fn it_projects_non_zero_counters_to_a_bitmap() {
+        // given
+        let mut counters = CountingBitmap::<8>::new();
+        counters.set(0, 200);
+        counters.set(7, 1);
+        counters.set(9, 300);
+        counters.set(1_000_000, 0);
+
+        // when
+        let bitmap = counters.to_bitmap();
+
+        // then
+        assert_eq!(counters.get(9), 255);
+        assert_eq!(bitmap.iter().collect::<Vec<u32>>(), vec![0, 7, 9]);
+        assert_eq!(bitmap.count_ones(), 3);
+    }
+
+    #[test]
+    fn it_compares_counters_without_trailing_zero_u32s() {
+        // given
+        let mut counters = CountingBitmap::<4>::new();
+        let hash = |counters: &CountingBitmap<4>| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            counters.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // when
+        counters.increment(1_000);
+        counters.decrement(1_000);
+
+        // then
+        assert_eq!(counters, CountingBitmap::<4>::new());
+        assert_eq!(hash(&counters), hash(&CountingBitmap::<4>::new()));
+
+        counters.increment(3);
+        assert_ne!(counters, CountingBitmap::<4>::new());
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{Bitmap, SimpleBitmap};
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// Wrapper around a [SimpleBitmap] that shares its u32s between clones.
+///
+/// Cloning only increments a reference count, so snapshots can be handed to other threads cheaply. The u32s are copied
+/// on the first modification of a clone whose storage is still shared.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CowBitmap {
+    bitmap: Arc<SimpleBitmap>,
+}
+
+impl CowBitmap {
+    pub fn new() -> Self {
+        Self::from(SimpleBitmap::new())
+    }
+
+    /// Returns the wrapped bitmap.
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Returns the wrapped bitmap for modification, copying it first if it is shared with other clones.
+    pub fn make_mut(&mut self) -> &mut SimpleBitmap {
+        Arc::make_mut(&mut self.bitmap)
+    }
+
+    /// Unwraps the bitmap, copying it if it is shared with other clones.
+    pub fn into_inner(self) -> SimpleBitmap {
+        Arc::unwrap_or_clone(self.bitmap)
+    }
+
+    /// Returns `true` if the storage is shared with other clones, so the next modification copies it.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.bitmap) > 1
+    }
+
+    /// Captures the current values, e.g. before trying out modifications. Only a reference count is incremented, the
+    /// u32s are copied on the next modification of this bitmap.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            bitmap: Arc::clone(&self.bitmap),
+        }
+    }
+
+    /// Reverts to the values captured by the snapshot, dropping all modifications made since.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.bitmap = snapshot.bitmap;
+    }
+}
+
+/// Read-only values of a [CowBitmap] at the time the snapshot was taken.
+///
+/// Created by [CowBitmap::snapshot].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    bitmap: Arc<SimpleBitmap>,
+}
+
+impl Snapshot {
+    /// Returns the captured values.
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+}
+
+/// Bounded history of [Snapshot]s of a [CowBitmap] that modifications can be undone with, keeping only the most recent
+/// ones.
+#[derive(Clone, Debug)]
+pub struct UndoHistory {
+    snapshots: VecDeque<Snapshot>,
+    limit: usize,
+}
+
+impl UndoHistory {
+    /// Creates a history that keeps at most `limit` snapshots.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            limit,
+        }
+    }
+
+    /// Returns the number of snapshots that can be undone.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if there is nothing to undo.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Records the current values of the bitmap, dropping the oldest snapshot if the history is full.
+    pub fn checkpoint(&mut self, bitmap: &CowBitmap) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.limit {
+            self.snapshots.pop_front();
+        }
+
+        self.snapshots.push_back(bitmap.snapshot());
+    }
+
+    /// Restores the bitmap to the most recent checkpoint and returns `true`, or returns `false` if there is none.
+    pub fn undo(&mut self, bitmap: &mut CowBitmap) -> bool {
+        match self.snapshots.pop_back() {
+            Some(snapshot) => {
+                bitmap.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl From<SimpleBitmap> for CowBitmap {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        Self {
+            bitmap: Arc::new(bitmap),
+        }
+    }
+}
+
+impl Bitmap for CowBitmap {
+    fn set(&mut self, index: u32) {
+        // setting an already present value doesn't change anything, so the storage doesn't have to be copied
+        if !self.bitmap.get(index) {
+            self.make_mut().set(index);
+        }
+    }
+
+    fn clear(&mut self, index: u32) {
+        if self.bitmap.get(index) {
+            self.make_mut().clear(index);
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bitmap.get(index)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.bitmap.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bitmap.count_ones()
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.bitmap.is_subset(other.bitmap())
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.bitmap.is_disjoint(other.bitmap())
+    }
+
+    fn intersection_len(&self, other: &Self) -> u64 {
+        self.bitmap.intersection_len(other.bitmap())
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        *self.make_mut() |= other.bitmap();
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        *self.make_mut() &= other.bitmap();
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.make_mut().difference_with(other.bitmap());
+    }
+}
+
+impl BitOr for CowBitmap {
+    type Output = CowBitmap;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        *self.make_mut() |= rhs.bitmap();
+        self
+    }
+}
+
+impl BitAnd for CowBitmap {
+    type Output = CowBitmap;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        *self.make_mut() &= rhs.bitmap();
+        self
+    }
+}
+
+impl BitXor for CowBitmap {
+    type Output = CowBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        CowBitmap::from(self.into_inner() ^ rhs.into_inner())
+    }
+}
+
+impl Sub for CowBitmap {
+    type Output = CowBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.make_mut().difference_with(rhs.bitmap());
+        self
+    }
+}
+
+impl FromIterator<u32> for CowBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        CowBitmap::from(iter.into_iter().collect::<SimpleBitmap>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn it_shares_storage_until_modified() {
+        // given
+        let original: CowBitmap = (0..1_000).collect();
+
+        // when
+        let snapshot = original.clone();
+        let mut modified = original.clone();
+        modified.set(3);
+        let shared_after_noop = modified.is_shared();
+        modified.set(5_000);
+
+        // then
+        assert!(shared_after_noop);
+        assert!(!modified.is_shared());
+        assert!(snapshot.is_shared());
+        assert!(!snapshot.get(5_000));
+        assert!(modified.get(5_000));
+        assert_eq!(original, snapshot);
+    }
+
+    #[test]
+    fn it_hands_snapshots_to_other_threads() {
+        // given
+        let mut bm: CowBitmap = (0..100).collect();
+        let snapshot = bm.clone();
+
+        // when
+        let count_in_thread = thread::spawn(move || snapshot.count_ones());
+        bm.clear(0);
+
+        // then
+        assert_eq!(count_in_thread.join().unwrap(), 100);
+        assert_eq!(bm.count_ones(), 99);
+    }
+
+    #[test]
+    fn it_combines_bitmaps() {
+        // given
+        let left: CowBitmap = [1, 2, 3].into_iter().collect();
+        let right: CowBitmap = [3, 4].into_iter().collect();
+
+        // then
+        assert_eq!((left.clone() | right.clone()).count_ones(), 4);
+        assert_eq!((left.clone() & right.clone()).count_ones(), 1);
+        assert_eq!((left.clone() ^ right.clone()).count_ones(), 3);
+        assert_eq!(
+            (left - right).bitmap().iter().collect::<Vec<u32>>(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn it_restores_snapshots() {
+        // given
+        let mut bm: CowBitmap = [1, 2, 3].into_iter().collect();
+        let snapshot = bm.snapshot();
+
+        // when
+        bm.clear(1);
+        bm.set(5_000);
+        bm.restore(snapshot.clone());
+
+        // then
+        assert_eq!(bm.bitmap(), snapshot.bitmap());
+        assert_eq!(bm.bitmap().iter().collect::<Vec<u32>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn it_undoes_modifications_up_to_the_limit() {
+        // given
+        let mut bm = CowBitmap::new();
+        let mut history = UndoHistory::new(2);
+
+        // when
+        for index in 0..3 {
+            history.checkpoint(&bm);
+            bm.set(index);
+        }
+
+        // then
+        assert_eq!(history.len(), 2);
+        assert!(history.undo(&mut bm));
+        assert_eq!(bm.bitmap().iter().collect::<Vec<u32>>(), vec![0, 1]);
+        assert!(history.undo(&mut bm));
+        assert_eq!(bm.bitmap().iter().collect::<Vec<u32>>(), vec![0]);
+        assert!(!history.undo(&mut bm));
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn it_keeps_every_snapshot_of_an_unbounded_history() {
+        // given
+        let mut bm = CowBitmap::new();
+        let mut history = UndoHistory::new(usize::MAX);
+
+        // when
+        for index in 0..3 {
+            history.checkpoint(&bm);
+            bm.set(index);
+        }
+
+        // then
+        assert_eq!(history.len(), 3);
+        assert!(history.undo(&mut bm));
+        assert_eq!(bm.bitmap().iter().collect::<Vec<u32>>(), vec![0, 1]);
+    }
+}
@@ -0,0 +1,103 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// Error returned when decoding a bitmap from its serialized form fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input ended before the bitmap was complete.
+    UnexpectedEnd,
+    /// The input doesn't start with the cookie or magic number of the expected format.
+    UnknownFormat,
+    /// The input was written in a newer version of the format, which is not supported.
+    UnsupportedVersion(u16),
+    /// The input is complete, but doesn't describe a valid bitmap.
+    Invalid(&'static str),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "input ended before the bitmap was complete"),
+            DecodeError::UnknownFormat => write!(f, "input is not in the expected format"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "version {version} of the format is not supported")
+            }
+            DecodeError::Invalid(reason) => write!(f, "input is not a valid bitmap: {reason}"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Error returned when a value above the maximum index of a [CappedBitmap](crate::CappedBitmap) is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexOutOfRange {
+    /// The index that was rejected.
+    pub index: u32,
+    /// The highest index the bitmap accepts.
+    pub max_index: u32,
+}
+
+impl Display for IndexOutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "index {} is above the maximum index {} of the bitmap",
+            self.index, self.max_index
+        )
+    }
+}
+
+impl Error for IndexOutOfRange {}
+
+/// Reads little-endian integers from the front of a byte slice.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the next `len` bytes.
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if len > self.bytes.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    /// Fails unless all bytes have been read.
+    pub(crate) fn finish(self) -> Result<(), DecodeError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::Invalid("trailing bytes after the bitmap"))
+        }
+    }
+
+    fn array<const LEN: usize>(&mut self) -> Result<[u8; LEN], DecodeError> {
+        Ok(self
+            .take(LEN)?
+            .try_into()
+            .expect("exactly LEN bytes were taken"))
+    }
+}
@@ -0,0 +1,527 @@
+use crate::Bitmap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// Highest number of fill u32s a single marker can describe.
+const MAX_FILL_LEN: u32 = (1 << 16) - 1;
+
+/// Highest number of literal u32s that can follow a single marker.
+const MAX_LITERALS_LEN: u32 = (1 << 15) - 1;
+
+/// Compressed implementation of a [Bitmap] using enhanced word-aligned hybrid (EWAH) run-length encoding.
+///
+/// The uncompressed u32s are stored as a sequence of markers, each followed by literal u32s. A marker describes a fill
+/// of u32s that are either all zeros or all ones, followed by a number of literal u32s that are stored as they are:
+///
+/// ```text
+/// bit  0      fill bit, i.e. whether the fill u32s are all ones
+/// bits 1-16   number of fill u32s
+/// bits 17-31  number of literal u32s following the marker
+/// ```
+///
+/// Long runs of absent or present values therefore take a single marker, and all operations between two bitmaps are
+/// computed while streaming over the compressed forms. Setting values in ascending order appends to the encoding,
+/// whereas setting or clearing values in the middle re-encodes the bitmap.
+#[derive(Clone, Debug)]
+pub struct EwahBitmap {
+    buffer: Vec<u32>,
+    // position of the marker that describes the end of the buffer
+    last_marker: usize,
+    // number of uncompressed u32s described by the buffer
+    len_words: usize,
+}
+
+impl EwahBitmap {
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![0],
+            last_marker: 0,
+            len_words: 0,
+        }
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.words().all(|(_, word)| word == 0)
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> EwahOnes<'_> {
+        EwahOnes {
+            words: self.words(),
+            bucket: 0,
+            bucket_base: 0,
+        }
+    }
+
+    /// Returns the number of u32s the encoding takes, including markers.
+    pub fn compressed_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Appends `count` u32s that are either all zeros or all ones.
+    fn push_fill(&mut self, fill_bit: bool, mut count: usize) {
+        while count > 0 {
+            let marker = self.buffer[self.last_marker];
+
+            // the last marker can be extended if no literals follow it and its fill is either empty or of the same kind
+            if literals_len(marker) == 0
+                && (fill_len(marker) == 0 || self::fill_bit(marker) == fill_bit)
+                && fill_len(marker) < MAX_FILL_LEN
+            {
+                let added = count.min((MAX_FILL_LEN - fill_len(marker)) as usize);
+                self.buffer[self.last_marker] =
+                    encode_marker(fill_bit, fill_len(marker) + added as u32, 0);
+
+                self.len_words += added;
+                count -= added;
+            } else {
+                self.last_marker = self.buffer.len();
+                self.buffer.push(0);
+            }
+        }
+    }
+
+    /// Appends a single u32, folding it into a fill if it is all zeros or all ones.
+    fn push_word(&mut self, word: u32) {
+        if word == 0 || word == u32::MAX {
+            self.push_fill(word == u32::MAX, 1);
+            return;
+        }
+
+        let marker = self.buffer[self.last_marker];
+
+        if literals_len(marker) == MAX_LITERALS_LEN {
+            self.last_marker = self.buffer.len();
+            self.buffer.push(0);
+        }
+
+        let marker = self.buffer[self.last_marker];
+        self.buffer[self.last_marker] =
+            encode_marker(fill_bit(marker), fill_len(marker), literals_len(marker) + 1);
+        self.buffer.push(word);
+        self.len_words += 1;
+    }
+
+    /// Appends `count` copies of the same u32.
+    fn push_words(&mut self, word: u32, count: usize) {
+        if word == 0 || word == u32::MAX {
+            self.push_fill(word == u32::MAX, count);
+        } else {
+            for _ in 0..count {
+                self.push_word(word);
+            }
+        }
+    }
+
+    /// Iterates over all uncompressed u32s that are not part of a zero fill, together with their index.
+    fn words(&self) -> Words<'_> {
+        Words {
+            cursor: Cursor::new(&self.buffer),
+            word_index: 0,
+        }
+    }
+
+    /// Builds a bitmap holding a single value.
+    fn single(index: u32) -> Self {
+        let mut bitmap = EwahBitmap::new();
+        bitmap.push_fill(false, (index / 32) as usize);
+        bitmap.push_word(0b1 << (index & 0b11111));
+
+        bitmap
+    }
+
+    /// Combines both bitmaps u32 by u32 while streaming over their encodings. Fills on both sides are combined as a
+    /// whole without expanding them.
+    fn combine(&self, other: &EwahBitmap, op: impl Fn(u32, u32) -> u32) -> EwahBitmap {
+        let mut combined = EwahBitmap::new();
+        let mut left = Cursor::new(&self.buffer);
+        let mut right = Cursor::new(&other.buffer);
+
+        loop {
+            left.normalize();
+            right.normalize();
+
+            if left.is_exhausted() && right.is_exhausted() {
+                break;
+            }
+
+            if left.fill_remaining > 0 && right.fill_remaining > 0 {
+                // an exhausted side acts as an endless zero fill, so the other side determines the length
+                let count = left.fill_remaining.min(right.fill_remaining);
+                let word = op(left.fill_word(), right.fill_word());
+
+                left.skip_fill(count);
+                right.skip_fill(count);
+                combined.push_words(word, count);
+            } else {
+                combined.push_word(op(left.take_word(), right.take_word()));
+            }
+        }
+
+        combined
+    }
+}
+
+fn encode_marker(fill_bit: bool, fill_len: u32, literals_len: u32) -> u32 {
+    u32::from(fill_bit) | (fill_len << 1) | (literals_len << 17)
+}
+
+fn fill_bit(marker: u32) -> bool {
+    marker & 0b1 == 1
+}
+
+fn fill_len(marker: u32) -> u32 {
+    (marker >> 1) & MAX_FILL_LEN
+}
+
+fn literals_len(marker: u32) -> u32 {
+    marker >> 17
+}
+
+/// Streams over an encoding, one fill or literal u32 at a time. Once the encoding is exhausted, the cursor acts as an
+/// endless zero fill.
+struct Cursor<'a> {
+    remaining: &'a [u32],
+    fill_bit: bool,
+    fill_remaining: usize,
+    literals: &'a [u32],
+    exhausted: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buffer: &'a [u32]) -> Self {
+        Self {
+            remaining: buffer,
+            fill_bit: false,
+            fill_remaining: 0,
+            literals: &[],
+            exhausted: false,
+        }
+    }
+
+    /// Loads the next marker if the current one is used up.
+    fn normalize(&mut self) {
+        while self.fill_remaining == 0 && self.literals.is_empty() {
+            let Some((marker, remaining)) = self.remaining.split_first() else {
+                self.exhausted = true;
+                self.fill_bit = false;
+                self.fill_remaining = usize::MAX;
+                return;
+            };
+
+            let (literals, remaining) = remaining.split_at(literals_len(*marker) as usize);
+
+            self.fill_bit = fill_bit(*marker);
+            self.fill_remaining = fill_len(*marker) as usize;
+            self.literals = literals;
+            self.remaining = remaining;
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    fn fill_word(&self) -> u32 {
+        if self.fill_bit { u32::MAX } else { 0 }
+    }
+
+    fn skip_fill(&mut self, count: usize) {
+        if !self.exhausted {
+            self.fill_remaining -= count;
+        }
+    }
+
+    /// Takes the next uncompressed u32. The cursor has to be normalized.
+    fn take_word(&mut self) -> u32 {
+        if self.fill_remaining > 0 {
+            let word = self.fill_word();
+            self.skip_fill(1);
+            word
+        } else {
+            let (word, literals) = self.literals.split_first().expect("cursor is normalized");
+            self.literals = literals;
+            *word
+        }
+    }
+}
+
+/// Iterator over the uncompressed u32s of an encoding that are not part of a zero fill, together with their index.
+struct Words<'a> {
+    cursor: Cursor<'a>,
+    word_index: usize,
+}
+
+impl Iterator for Words<'_> {
+    type Item = (usize, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.cursor.normalize();
+
+            if self.cursor.is_exhausted() {
+                return None;
+            }
+
+            // zero fills are skipped as a whole
+            if !self.cursor.fill_bit && self.cursor.fill_remaining > 0 {
+                self.word_index += self.cursor.fill_remaining;
+                self.cursor.fill_remaining = 0;
+                continue;
+            }
+
+            let word_index = self.word_index;
+            self.word_index += 1;
+
+            return Some((word_index, self.cursor.take_word()));
+        }
+    }
+}
+
+impl Bitmap for EwahBitmap {
+    fn set(&mut self, index: u32) {
+        let u32_index = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        // values beyond the end can be appended to the encoding
+        if u32_index >= self.len_words {
+            self.push_fill(false, u32_index - self.len_words);
+            self.push_word(0b1 << bit_index_in_u32);
+        } else if !self.get(index) {
+            *self = self.combine(&EwahBitmap::single(index), |left, right| left | right);
+        }
+    }
+
+    fn clear(&mut self, index: u32) {
+        if self.get(index) {
+            *self = self.combine(&EwahBitmap::single(index), |left, right| left & !right);
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        let u32_index = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        self.words()
+            .take_while(|(word_index, _)| *word_index <= u32_index)
+            .find(|(word_index, _)| *word_index == u32_index)
+            .is_some_and(|(_, word)| (word >> bit_index_in_u32) & 0b1 == 1)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        let mut count = 0;
+        let mut cursor = Cursor::new(&self.buffer);
+
+        loop {
+            cursor.normalize();
+            if cursor.is_exhausted() {
+                return count;
+            }
+
+            // fills are counted as a whole
+            if cursor.fill_bit {
+                count += cursor.fill_remaining as u64 * 32;
+            }
+            count += cursor
+                .literals
+                .iter()
+                .map(|word| u64::from(word.count_ones()))
+                .sum::<u64>();
+
+            cursor.fill_remaining = 0;
+            cursor.literals = &[];
+        }
+    }
+}
+
+impl BitOr for EwahBitmap {
+    type Output = EwahBitmap;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, |left, right| left | right)
+    }
+}
+
+impl BitAnd for EwahBitmap {
+    type Output = EwahBitmap;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, |left, right| left & right)
+    }
+}
+
+impl BitXor for EwahBitmap {
+    type Output = EwahBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, |left, right| left ^ right)
+    }
+}
+
+impl Sub for EwahBitmap {
+    type Output = EwahBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(&rhs, |left, right| left & !right)
+    }
+}
+
+impl PartialEq for EwahBitmap {
+    fn eq(&self, other: &Self) -> bool {
+        // bitmaps with the same values are equal, no matter how they are encoded
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for EwahBitmap {}
+
+impl FromIterator<u32> for EwahBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bitmap = EwahBitmap::new();
+        bitmap.extend(iter);
+
+        bitmap
+    }
+}
+
+impl Extend<u32> for EwahBitmap {
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        let mut iter = iter.into_iter().peekable();
+
+        while let Some(index) = iter.next() {
+            let u32_index = (index / 32) as usize;
+
+            // sorted values beyond the end are gathered into whole u32s and appended to the encoding
+            if u32_index >= self.len_words {
+                let mut word = 0b1 << (index & 0b11111);
+                while let Some(next) = iter.next_if(|next| (next / 32) as usize == u32_index) {
+                    word |= 0b1 << (next & 0b11111);
+                }
+
+                self.push_fill(false, u32_index - self.len_words);
+                self.push_word(word);
+            } else {
+                self.set(index);
+            }
+        }
+    }
+}
+
+/// Iterator over the indices of the values present in an [EwahBitmap], in ascending order.
+///
+/// Created by [EwahBitmap::iter].
+pub struct EwahOnes<'a> {
+    words: Words<'a>,
+    bucket: u32,
+    bucket_base: u32,
+}
+
+impl Iterator for EwahOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket == 0 {
+            let (word_index, word) = self.words.next()?;
+            self.bucket = word;
+            self.bucket_base = word_index as u32 * 32;
+        }
+
+        let bit_index_in_u32 = self.bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.bucket &= self.bucket - 1;
+
+        Some(self.bucket_base + bit_index_in_u32)
+    }
+}
+
+impl<'a> IntoIterator for &'a EwahBitmap {
+    type Item = u32;
+    type IntoIter = EwahOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_combines_like_simple_bitmaps;
+
+    #[test]
+    fn it_sets_and_gets_bits() {
+        // given
+        let mut bm = EwahBitmap::new();
+
+        // when
+        bm.set(31);
+        bm.set(32);
+        bm.set(100_000);
+        bm.set(5);
+        bm.clear(32);
+
+        // then
+        assert!(!bm.get(0));
+        assert!(bm.get(5));
+        assert!(bm.get(31));
+        assert!(!bm.get(32));
+        assert!(bm.get(100_000));
+        assert!(!bm.get(100_001));
+        assert_eq!(bm.count_ones(), 3);
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![5, 31, 100_000]);
+    }
+
+    #[test]
+    fn it_compresses_long_runs() {
+        // given
+        let indices = (0..1_000_000).chain(3_000_000..4_000_000);
+
+        // when
+        let bm: EwahBitmap = indices.clone().collect();
+
+        // then
+        assert!(bm.compressed_len() < 100);
+        assert_eq!(bm.count_ones(), 2_000_000);
+        assert!(bm.get(3_500_000));
+        assert!(!bm.get(2_000_000));
+        assert!(bm.iter().eq(indices));
+    }
+
+    #[test]
+    fn it_combines_bitmaps_like_simple_bitmaps() {
+        // given
+        let left: Vec<u32> = (0..100_000)
+            .filter(|index| index % 3 == 0)
+            .chain(200_000..400_000)
+            .collect();
+        let right: Vec<u32> = (50_000..300_000).filter(|index| index % 64 < 40).collect();
+
+        // then
+        assert_combines_like_simple_bitmaps::<EwahBitmap>(&left, &right);
+    }
+
+    #[test]
+    fn it_compares_bitmaps_by_their_values() {
+        // given
+        let mut left = EwahBitmap::new();
+        left.set(3);
+        left.set(1000);
+        left.clear(1000);
+
+        let mut right = EwahBitmap::new();
+        right.set(3);
+
+        // then
+        assert_eq!(left, right);
+        assert!(EwahBitmap::new().is_empty());
+        assert!(!left.is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+//! C interface to [SimpleBitmap], so C and C++ services can link against this crate.
+//!
+//! Bitmaps are passed as pointers to the opaque [BitmapHandle]. Every handle returned by a function of this module is
+//! owned by the caller and has to be released with [bitmap_free].
+//!
+//! The `bitmaps-ffi` package of this repository builds the module into a static and a dynamic library and declares
+//! its functions in `ffi/include/bitmaps.h`, which has to be kept in sync with this module.
+
+use crate::{Bitmap, SimpleBitmap};
+use std::{ptr, slice};
+
+/// Opaque handle of a bitmap, only ever accessed through pointers.
+#[repr(C)]
+pub struct BitmapHandle {
+    bitmap: SimpleBitmap,
+}
+
+/// Bytes exported by [bitmap_to_bytes], to be released with [bitmap_bytes_free].
+#[repr(C)]
+pub struct BitmapBytes {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+fn into_handle(bitmap: SimpleBitmap) -> *mut BitmapHandle {
+    Box::into_raw(Box::new(BitmapHandle { bitmap }))
+}
+
+/// Creates an empty bitmap.
+#[unsafe(no_mangle)]
+pub extern "C" fn bitmap_new() -> *mut BitmapHandle {
+    into_handle(SimpleBitmap::new())
+}
+
+/// Releases a bitmap. Passing a null pointer does nothing.
+///
+/// # Safety
+///
+/// The handle must be null or returned by this module and not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_free(handle: *mut BitmapHandle) {
+    if !handle.is_null() {
+        // SAFETY: the caller guarantees that the handle was created by Box::into_raw and is released only once
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+/// Sets the presence of a value.
+///
+/// # Safety
+///
+/// The handle must be valid and not be accessed by anything else during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_set(handle: *mut BitmapHandle, index: u32) {
+    // SAFETY: the caller guarantees that the handle is valid and exclusive
+    unsafe { &mut *handle }.bitmap.set(index);
+}
+
+/// Clears the presence of a value.
+///
+/// # Safety
+///
+/// The handle must be valid and not be accessed by anything else during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_clear(handle: *mut BitmapHandle, index: u32) {
+    // SAFETY: the caller guarantees that the handle is valid and exclusive
+    unsafe { &mut *handle }.bitmap.clear(index);
+}
+
+/// Returns whether a value is present.
+///
+/// # Safety
+///
+/// The handle must be valid and not be modified during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_get(handle: *const BitmapHandle, index: u32) -> bool {
+    // SAFETY: the caller guarantees that the handle is valid
+    unsafe { &*handle }.bitmap.get(index)
+}
+
+/// Counts the values that are present.
+///
+/// # Safety
+///
+/// The handle must be valid and not be modified during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_count(handle: *const BitmapHandle) -> u64 {
+    // SAFETY: the caller guarantees that the handle is valid
+    unsafe { &*handle }.bitmap.count_ones()
+}
+
+/// Returns a new bitmap with the values present in either bitmap.
+///
+/// # Safety
+///
+/// Both handles must be valid and not be modified during the call. They may be the same handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_or(
+    left: *const BitmapHandle,
+    right: *const BitmapHandle,
+) -> *mut BitmapHandle {
+    // SAFETY: the caller guarantees that both handles are valid
+    let (left, right) = unsafe { (&(*left).bitmap, &(*right).bitmap) };
+
+    let mut union = left.clone();
+    union |= right;
+
+    into_handle(union)
+}
+
+/// Serializes the bitmap into the binary format of [SimpleBitmap::to_bytes].
+///
+/// # Safety
+///
+/// The handle must be valid and not be modified during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_to_bytes(handle: *const BitmapHandle) -> BitmapBytes {
+    // SAFETY: the caller guarantees that the handle is valid
+    let bytes = unsafe { &*handle }.bitmap.to_bytes().into_boxed_slice();
+
+    let len = bytes.len();
+    BitmapBytes {
+        data: Box::into_raw(bytes).cast(),
+        len,
+    }
+}
+
+/// Releases bytes exported by [bitmap_to_bytes].
+///
+/// # Safety
+///
+/// The bytes must be returned by [bitmap_to_bytes], unchanged and not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_bytes_free(bytes: BitmapBytes) {
+    // SAFETY: the caller guarantees that the bytes are an unreleased boxed slice of bitmap_to_bytes
+    drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(bytes.data, bytes.len)) });
+}
+
+/// Deserializes a bitmap from the binary format of [SimpleBitmap::to_bytes]. Returns a null pointer if the bytes are
+/// malformed.
+///
+/// # Safety
+///
+/// The data must point to `len` readable bytes, it may only be null if `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitmap_from_bytes(data: *const u8, len: usize) -> *mut BitmapHandle {
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees that the data points to len readable bytes
+        unsafe { slice::from_raw_parts(data, len) }
+    };
+
+    match SimpleBitmap::from_bytes(bytes) {
+        Ok(bitmap) => into_handle(bitmap),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_manages_bitmaps_through_handles() {
+        unsafe {
+            // given
+            let left = bitmap_new();
+            let right = bitmap_new();
+            bitmap_set(left, 3);
+            bitmap_set(left, 4);
+            bitmap_clear(left, 4);
+            bitmap_set(right, 100);
+
+            // when
+            let union = bitmap_or(left, right);
+            let bytes = bitmap_to_bytes(union);
+            let copy = bitmap_from_bytes(bytes.data, bytes.len);
+
+            // then
+            assert!(bitmap_get(copy, 3));
+            assert!(!bitmap_get(copy, 4));
+            assert_eq!(bitmap_count(copy), 2);
+            assert!(bitmap_from_bytes(ptr::null(), 0).is_null());
+
+            for handle in [left, right, union, copy] {
+                bitmap_free(handle);
+            }
+            bitmap_bytes_free(bytes);
+        }
+    }
+}
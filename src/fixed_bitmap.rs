@@ -0,0 +1,279 @@
+use crate::{Bitmap, Ones};
+use core::iter;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+
+/// Fixed-size implementation of a [Bitmap] that stores its `WORDS` u32s inline, without any heap allocation.
+///
+/// It can hold values below `WORDS * 32`. As the size is fixed, the complement via [Not] is well-defined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FixedBitmap<const WORDS: usize> {
+    bits: [u32; WORDS],
+}
+
+impl<const WORDS: usize> FixedBitmap<WORDS> {
+    /// Number of values the bitmap can hold.
+    pub const CAPACITY: u64 = WORDS as u64 * 32;
+
+    pub const fn new() -> Self {
+        Self { bits: [0; WORDS] }
+    }
+
+    /// Creates a bitmap from its u32s, where bit `i` of u32 `j` holds the value `j * 32 + i`.
+    pub const fn from_words(bits: [u32; WORDS]) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the u32s backing this bitmap.
+    pub const fn words(&self) -> &[u32; WORDS] {
+        &self.bits
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|bucket| *bucket == 0)
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// Returns the u32 holding the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not below [FixedBitmap::CAPACITY].
+    fn bucket_mut(&mut self, index: u32) -> &mut u32 {
+        assert!(
+            u64::from(index) < Self::CAPACITY,
+            "index {index} is out of range for a bitmap of {} values",
+            Self::CAPACITY
+        );
+
+        &mut self.bits[(index / 32) as usize]
+    }
+}
+
+impl<const WORDS: usize> Bitmap for FixedBitmap<WORDS> {
+    /// # Panics
+    ///
+    /// Panics if the index is not below [FixedBitmap::CAPACITY].
+    fn set(&mut self, index: u32) {
+        *self.bucket_mut(index) |= 0b1 << (index & 0b11111);
+    }
+
+    /// Values at or above [FixedBitmap::CAPACITY] can't be present, so clearing them does nothing.
+    fn clear(&mut self, index: u32) {
+        if let Some(bucket) = self.bits.get_mut((index / 32) as usize) {
+            *bucket &= !(0b1 << (index & 0b11111));
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bits
+            .get((index / 32) as usize)
+            .is_some_and(|bucket| (bucket >> (index & 0b11111)) & 0b1 == 1)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bits
+            .iter()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum()
+    }
+}
+
+impl<const WORDS: usize> BitOrAssign for FixedBitmap<WORDS> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (left, right) in iter::zip(&mut self.bits, rhs.bits) {
+            *left |= right;
+        }
+    }
+}
+
+impl<const WORDS: usize> BitAndAssign for FixedBitmap<WORDS> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for (left, right) in iter::zip(&mut self.bits, rhs.bits) {
+            *left &= right;
+        }
+    }
+}
+
+impl<const WORDS: usize> BitXorAssign for FixedBitmap<WORDS> {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for (left, right) in iter::zip(&mut self.bits, rhs.bits) {
+            *left ^= right;
+        }
+    }
+}
+
+impl<const WORDS: usize> SubAssign for FixedBitmap<WORDS> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (left, right) in iter::zip(&mut self.bits, rhs.bits) {
+            *left &= !right;
+        }
+    }
+}
+
+impl<const WORDS: usize> BitOr for FixedBitmap<WORDS> {
+    type Output = FixedBitmap<WORDS>;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl<const WORDS: usize> BitAnd for FixedBitmap<WORDS> {
+    type Output = FixedBitmap<WORDS>;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl<const WORDS: usize> BitXor for FixedBitmap<WORDS> {
+    type Output = FixedBitmap<WORDS>;
+
+    fn bitxor(mut self, rhs: Self) -> Self::Output {
+        self ^= rhs;
+        self
+    }
+}
+
+impl<const WORDS: usize> Sub for FixedBitmap<WORDS> {
+    type Output = FixedBitmap<WORDS>;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const WORDS: usize> Not for FixedBitmap<WORDS> {
+    type Output = FixedBitmap<WORDS>;
+
+    /// Returns the bitmap holding exactly the values below [FixedBitmap::CAPACITY] that are absent from `self`.
+    fn not(mut self) -> Self::Output {
+        for bucket in &mut self.bits {
+            *bucket = !*bucket;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> FromIterator<u32> for FixedBitmap<WORDS> {
+    /// # Panics
+    ///
+    /// Panics if any index is not below [FixedBitmap::CAPACITY].
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bitmap = FixedBitmap::new();
+        bitmap.extend(iter);
+
+        bitmap
+    }
+}
+
+impl<const WORDS: usize> Extend<u32> for FixedBitmap<WORDS> {
+    /// # Panics
+    ///
+    /// Panics if any index is not below [FixedBitmap::CAPACITY].
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for index in iter {
+            self.set(index);
+        }
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a FixedBitmap<WORDS> {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMES: FixedBitmap<1> = FixedBitmap::from_words([0b10100000100010100010100010101100]);
+
+    #[test]
+    fn it_sets_and_gets_bits() {
+        // given
+        let mut bm = FixedBitmap::<2>::new();
+
+        // when
+        bm.set(0);
+        bm.set(63);
+        bm.set(5);
+        bm.clear(5);
+
+        // then
+        assert!(bm.get(0));
+        assert!(bm.get(63));
+        assert!(!bm.get(5));
+        assert!(!bm.get(64));
+        assert_eq!(bm.count_ones(), 2);
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![0, 63]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn it_rejects_indices_beyond_its_capacity() {
+        FixedBitmap::<2>::new().set(64);
+    }
+
+    #[test]
+    fn it_ignores_clearing_indices_beyond_its_capacity() {
+        // given
+        let mut bm: FixedBitmap<2> = FixedBitmap::new();
+        bm.set(63);
+
+        // when
+        bm.clear(64);
+        bm.clear(u32::MAX);
+
+        // then
+        assert!(!bm.remove(64));
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![63]);
+    }
+
+    #[test]
+    fn it_is_constructible_in_const_contexts() {
+        assert_eq!(
+            PRIMES.iter().collect::<Vec<u32>>(),
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31]
+        );
+    }
+
+    #[test]
+    fn it_combines_bitmaps() {
+        // given
+        let odd: FixedBitmap<1> = (1..32).step_by(2).collect();
+
+        // then
+        assert_eq!((PRIMES & odd).count_ones(), 10);
+        assert_eq!((PRIMES | odd).count_ones(), 17);
+        assert_eq!(
+            (PRIMES ^ odd).iter().collect::<Vec<u32>>(),
+            vec![1, 2, 9, 15, 21, 25, 27]
+        );
+        assert_eq!((PRIMES - odd).iter().collect::<Vec<u32>>(), vec![2]);
+        assert_eq!(!odd, (0..32).step_by(2).collect());
+        assert_eq!(
+            (!FixedBitmap::<3>::new()).count_ones(),
+            FixedBitmap::<3>::CAPACITY
+        );
+    }
+}
@@ -1,15 +1,445 @@
 #![allow(clippy::new_without_default)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
-pub use simple_bitmap::SimpleBitmap;
-use std::ops::BitOr;
+extern crate alloc;
 
+use alloc::vec::Vec;
+#[cfg(target_has_atomic = "64")]
+pub use atomic_bitmap::AtomicBitmap;
+pub use bit_grid::BitGrid;
+pub use bitmap_builder::BitmapBuilder;
+pub use bitmap_expr::BitmapExpr;
+pub use bitmap_index::BitmapIndex;
+pub use bitmap_ref::BitmapRef;
+pub use bloom_filter::BloomFilter;
+pub use bounded_bitmap::{BoundedBitmap, Slots};
+pub use capped_bitmap::CappedBitmap;
+pub use cardinality_sketch::CardinalitySketch;
+pub use counting_bitmap::CountingBitmap;
+#[cfg(target_has_atomic = "ptr")]
+pub use cow_bitmap::{CowBitmap, Snapshot, UndoHistory};
+pub use error::{DecodeError, IndexOutOfRange};
+pub use ewah_bitmap::{EwahBitmap, EwahOnes};
+pub use fixed_bitmap::FixedBitmap;
+#[doc(hidden)]
+pub use macros::BitmapIndices;
+#[cfg(feature = "mmap")]
+pub use mmap_bitmap::{MmapBitmap, MmapOnes};
+pub use packed_int_vec::{PackedIntVec, PackedInts};
+#[cfg(target_has_atomic = "ptr")]
+pub use persistent_bitmap::{PersistentBitmap, PersistentOnes};
+pub use rank_select_bitmap::RankSelectBitmap;
+pub use roaring_bitmap::{RoaringBitmap, RoaringOnes};
+pub use segmented_bitmap::{SegmentedBitmap, SegmentedOnes};
+#[cfg(feature = "std")]
+pub use sharded_bitmap::ShardedBitmap;
+pub use simple_bitmap::{
+    BitmapDelta, BitmapMemoryStats, BitmapStats, BitmapView, CombinedOnes, Cursor, Ones,
+    Representation, Runs, RunsInclusive, SimpleBitmap, ViewOnes, Zeros,
+};
+pub use simple_bitmap64::{Ones64, SimpleBitmap64};
+pub use sparse_bitmap::{SparseBitmap, SparseOnes};
+pub use tracked_bitmap::TrackedBitmap;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmBitmap;
+
+#[cfg(feature = "arrow")]
+mod arrow_interop;
+#[cfg(target_has_atomic = "64")]
+mod atomic_bitmap;
+mod bit_grid;
+mod bitmap_builder;
+mod bitmap_expr;
+mod bitmap_index;
+mod bitmap_ref;
+#[cfg(feature = "bitvec-interop")]
+mod bitvec_interop;
+pub mod bloom_filter;
+mod bounded_bitmap;
+mod capped_bitmap;
+mod cardinality_sketch;
+pub mod chunks;
+mod counting_bitmap;
+#[cfg(target_has_atomic = "ptr")]
+mod cow_bitmap;
+mod error;
+mod ewah_bitmap;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed_bitmap;
+#[macro_use]
+mod macros;
+#[cfg(feature = "mmap")]
+mod mmap_bitmap;
+mod packed_int_vec;
+#[cfg(target_has_atomic = "ptr")]
+mod persistent_bitmap;
+mod rank_select_bitmap;
+mod roaring_bitmap;
+#[cfg(feature = "roaring-interop")]
+mod roaring_interop;
+mod segmented_bitmap;
+#[cfg(feature = "std")]
+mod sharded_bitmap;
+pub mod similarity;
 mod simple_bitmap;
+mod simple_bitmap64;
+mod sparse_bitmap;
+mod tracked_bitmap;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 /// Describes the presence or absence of values.
-pub trait Bitmap: Sized + BitOr {
+///
+/// Implementations only have to provide setting, clearing and looking up single values and an iterator over the present
+/// values, the rest of the set API is derived from them. Implementations with a faster way, e.g. by combining whole
+/// u32s, override the provided methods.
+///
+/// The operators `|`, `&`, `^` and `-` are implemented by the bitmaps of this crate but not required by the trait, so
+/// generic code builds new bitmaps with [Bitmap::union], [Bitmap::intersection], [Bitmap::symmetric_difference] and
+/// [Bitmap::difference] instead.
+pub trait Bitmap {
     /// Sets the presence of a value at the given index.
+    ///
+    /// Implementations with a bounded capacity, like [FixedBitmap] and [BoundedBitmap], panic for indices beyond it.
+    /// Clearing or looking up such an index never panics, as the value can't be present.
     fn set(&mut self, index: u32);
 
+    /// Clears the presence of a value at the given index.
+    fn clear(&mut self, index: u32);
+
     /// Gets the presence or absence of a value at the given index.
     fn get(&self, index: u32) -> bool;
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    fn iter(&self) -> impl Iterator<Item = u32> + '_;
+
+    /// Sets the presence of a value at the given index and returns `true` if it was absent before, like
+    /// `HashSet::insert`.
+    fn insert(&mut self, index: u32) -> bool {
+        let absent = !self.get(index);
+        if absent {
+            self.set(index);
+        }
+
+        absent
+    }
+
+    /// Clears the presence of a value at the given index and returns `true` if it was present before, like
+    /// `HashSet::remove`.
+    fn remove(&mut self, index: u32) -> bool {
+        let present = self.get(index);
+        if present {
+            self.clear(index);
+        }
+
+        present
+    }
+
+    /// Counts the values that are present.
+    fn count_ones(&self) -> u64 {
+        self.iter().count() as u64
+    }
+
+    /// Returns `true` if no value is present.
+    fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Returns `true` if all values of this bitmap are also present in `other`.
+    fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|index| other.get(index))
+    }
+
+    /// Returns `true` if all values of `other` are also present in this bitmap.
+    fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if no value is present in both this bitmap and `other`.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        !self.iter().any(|index| other.get(index))
+    }
+
+    /// Counts the values present in both this bitmap and `other`, without building the intersection.
+    fn intersection_len(&self, other: &Self) -> u64 {
+        self.iter().filter(|index| other.get(*index)).count() as u64
+    }
+
+    /// Counts the values present in this bitmap or `other`, without building the union.
+    fn union_len(&self, other: &Self) -> u64 {
+        self.count_ones() + other.count_ones() - self.intersection_len(other)
+    }
+
+    /// Counts the values present in this bitmap but not in `other`, without building the difference.
+    fn difference_len(&self, other: &Self) -> u64 {
+        self.count_ones() - self.intersection_len(other)
+    }
+
+    /// Adds all values of `other` to this bitmap.
+    fn union_with(&mut self, other: &Self) {
+        for index in other.iter() {
+            self.set(index);
+        }
+    }
+
+    /// Removes all values from this bitmap that are absent from `other`.
+    fn intersect_with(&mut self, other: &Self) {
+        // the values are collected first, as the bitmap can't be modified while iterating over it
+        let absent: Vec<u32> = self.iter().filter(|index| !other.get(*index)).collect();
+        for index in absent {
+            self.clear(index);
+        }
+    }
+
+    /// Removes all values from this bitmap that are present in `other`.
+    fn difference_with(&mut self, other: &Self) {
+        let present: Vec<u32> = self.iter().filter(|index| other.get(*index)).collect();
+        for index in present {
+            self.clear(index);
+        }
+    }
+
+    /// Returns a new bitmap with the values present in this bitmap or `other`, like `|`.
+    fn union(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut union = self.clone();
+        union.union_with(other);
+
+        union
+    }
+
+    /// Returns a new bitmap with the values present in both this bitmap and `other`, like `&`.
+    fn intersection(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut intersection = self.clone();
+        intersection.intersect_with(other);
+
+        intersection
+    }
+
+    /// Returns a new bitmap with the values present in exactly one of this bitmap and `other`, like `^`.
+    fn symmetric_difference(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut symmetric_difference = self.clone();
+        for index in other.iter() {
+            if symmetric_difference.get(index) {
+                symmetric_difference.clear(index);
+            } else {
+                symmetric_difference.set(index);
+            }
+        }
+
+        symmetric_difference
+    }
+
+    /// Returns a new bitmap with the values present in this bitmap but not in `other`, like `-`.
+    fn difference(&self, other: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        let mut difference = self.clone();
+        difference.difference_with(other);
+
+        difference
+    }
+
+    /// Copies the present values into another implementation of a bitmap, e.g. to move the values of a
+    /// [SimpleBitmap] into a [RoaringBitmap] once they become sparse.
+    fn convert<B: Bitmap + FromIterator<u32>>(&self) -> B {
+        self.iter().collect()
+    }
+}
+
+/// Describes the presence or absence of values with 64-bit indices, the counterpart of [Bitmap] for values that don't
+/// fit into a u32.
+pub trait Bitmap64 {
+    /// Sets the presence of a value at the given index.
+    fn set(&mut self, index: u64);
+
+    /// Clears the presence of a value at the given index.
+    fn clear(&mut self, index: u64);
+
+    /// Gets the presence or absence of a value at the given index.
+    fn get(&self, index: u64) -> bool;
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    fn iter(&self) -> impl Iterator<Item = u64> + '_;
+
+    /// Counts the values that are present.
+    fn count_ones(&self) -> u64 {
+        self.iter().count() as u64
+    }
+}
+
+/// Helpers shared by the tests of the implementations of [Bitmap].
+#[cfg(test)]
+pub(crate) mod testing {
+    use crate::{Bitmap, SimpleBitmap};
+    use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+    /// Asserts that the operators of `B` yield the same values as the ones of [SimpleBitmap], in both directions.
+    pub(crate) fn assert_combines_like_simple_bitmaps<B>(left: &[u32], right: &[u32])
+    where
+        B: Bitmap
+            + FromIterator<u32>
+            + BitOr<Output = B>
+            + BitAnd<Output = B>
+            + BitXor<Output = B>
+            + Sub<Output = B>,
+    {
+        let bitmap = |indices: &[u32]| indices.iter().copied().collect::<B>();
+        let simple = |indices: &[u32]| indices.iter().copied().collect::<SimpleBitmap>();
+
+        for (left, right) in [(left, right), (right, left)] {
+            let expected = [
+                simple(left) | simple(right),
+                simple(left) & simple(right),
+                simple(left) ^ simple(right),
+                simple(left) - simple(right),
+            ];
+            let actual = [
+                bitmap(left) | bitmap(right),
+                bitmap(left) & bitmap(right),
+                bitmap(left) ^ bitmap(right),
+                bitmap(left) - bitmap(right),
+            ];
+
+            for (operator, (actual, expected)) in ["|", "&", "^", "-"]
+                .into_iter()
+                .zip(actual.iter().zip(&expected))
+            {
+                assert!(
+                    Bitmap::iter(actual).eq(expected.iter()),
+                    "{operator} differs from SimpleBitmap"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeSet;
+
+    /// Bitmap that only implements the required methods, so all others fall back to the provided ones.
+    #[derive(Clone, Debug, PartialEq)]
+    struct MinimalBitmap(BTreeSet<u32>);
+
+    impl Bitmap for MinimalBitmap {
+        fn set(&mut self, index: u32) {
+            self.0.insert(index);
+        }
+
+        fn clear(&mut self, index: u32) {
+            self.0.remove(&index);
+        }
+
+        fn get(&self, index: u32) -> bool {
+            self.0.contains(&index)
+        }
+
+        fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+            self.0.iter().copied()
+        }
+    }
+
+    impl FromIterator<u32> for MinimalBitmap {
+        fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+            Self(iter.into_iter().collect())
+        }
+    }
+
+    fn values(bitmap: &impl Bitmap) -> Vec<u32> {
+        bitmap.iter().collect()
+    }
+
+    #[test]
+    fn it_provides_the_set_api_from_the_required_methods() {
+        // given
+        let mut bm: MinimalBitmap = [1, 2, 3].into_iter().collect();
+        let other: MinimalBitmap = [3, 4].into_iter().collect();
+        let subset: MinimalBitmap = [1, 3].into_iter().collect();
+        let disjoint: MinimalBitmap = [100].into_iter().collect();
+
+        // then
+        assert!(bm.insert(5));
+        assert!(!bm.insert(5));
+        assert!(bm.remove(5));
+        assert!(!bm.remove(5));
+        assert_eq!(bm.count_ones(), 3);
+        assert!(!bm.is_empty());
+        assert!(MinimalBitmap::from_iter([]).is_empty());
+        assert!(subset.is_subset(&bm));
+        assert!(!bm.is_subset(&subset));
+        assert!(bm.is_superset(&subset));
+        assert!(bm.is_disjoint(&disjoint));
+        assert!(!bm.is_disjoint(&other));
+        assert_eq!(bm.intersection_len(&other), 1);
+        assert_eq!(bm.union_len(&other), 4);
+        assert_eq!(bm.difference_len(&other), 2);
+    }
+
+    #[test]
+    fn it_provides_the_set_operations_from_the_required_methods() {
+        // given
+        let bm: MinimalBitmap = [1, 2, 3].into_iter().collect();
+        let other: MinimalBitmap = [3, 4].into_iter().collect();
+
+        // then
+        assert_eq!(values(&bm.union(&other)), vec![1, 2, 3, 4]);
+        assert_eq!(values(&bm.intersection(&other)), vec![3]);
+        assert_eq!(values(&bm.symmetric_difference(&other)), vec![1, 2, 4]);
+        assert_eq!(values(&bm.difference(&other)), vec![1, 2]);
+
+        let mut union = bm.clone();
+        union.union_with(&other);
+        let mut intersection = bm.clone();
+        intersection.intersect_with(&other);
+        let mut difference = bm.clone();
+        difference.difference_with(&other);
+        assert_eq!(union, bm.union(&other));
+        assert_eq!(intersection, bm.intersection(&other));
+        assert_eq!(difference, bm.difference(&other));
+    }
+
+    #[test]
+    fn it_converts_between_implementations() {
+        // given
+        let bm: MinimalBitmap = [1, 40, 70_000].into_iter().collect();
+
+        // when
+        let simple: SimpleBitmap = bm.convert();
+
+        // then
+        assert_eq!(values(&simple), vec![1, 40, 70_000]);
+        assert_eq!(simple.convert::<MinimalBitmap>(), bm);
+    }
+
+    #[test]
+    fn it_matches_the_operators_of_the_implementations() {
+        // given
+        let bm: SimpleBitmap = [1, 2, 3, 40].into_iter().collect();
+        let other: SimpleBitmap = [3, 4, 40, 1_000].into_iter().collect();
+
+        // then
+        assert_eq!(Bitmap::union(&bm, &other), bm.clone() | other.clone());
+        assert_eq!(
+            Bitmap::intersection(&bm, &other),
+            bm.clone() & other.clone()
+        );
+        assert_eq!(
+            Bitmap::symmetric_difference(&bm, &other),
+            bm.clone() ^ other.clone()
+        );
+        assert_eq!(Bitmap::difference(&bm, &other), bm.clone() - other.clone());
+    }
 }
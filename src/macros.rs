@@ -0,0 +1,54 @@
+use crate::{Bitmap, SimpleBitmap};
+use core::ops::RangeInclusive;
+
+/// Creates a [SimpleBitmap] in which the given indices and inclusive ranges of indices are present, e.g.
+/// `bitmap![1, 6, 9..=20]`.
+#[macro_export]
+macro_rules! bitmap {
+    ($($indices:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut bitmap = $crate::SimpleBitmap::new();
+        $($crate::BitmapIndices::insert_into($indices, &mut bitmap);)*
+        bitmap
+    }};
+}
+
+/// Indices accepted by the [bitmap!] macro.
+#[doc(hidden)]
+pub trait BitmapIndices {
+    fn insert_into(self, bitmap: &mut SimpleBitmap);
+}
+
+impl BitmapIndices for u32 {
+    fn insert_into(self, bitmap: &mut SimpleBitmap) {
+        bitmap.set(self);
+    }
+}
+
+impl BitmapIndices for RangeInclusive<u32> {
+    fn insert_into(self, bitmap: &mut SimpleBitmap) {
+        if self.is_empty() {
+            return;
+        }
+
+        // the end is set on its own, as it may be u32::MAX, which can't be the exclusive end of a range
+        let (start, end) = self.into_inner();
+        bitmap.set_range(start..end);
+        bitmap.set(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SimpleBitmap;
+
+    #[test]
+    fn it_builds_bitmaps_from_indices_and_ranges() {
+        // given
+        let bm = bitmap![1, 6, 9..=11, 40..=40,];
+
+        // then
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![1, 6, 9, 10, 11, 40]);
+        assert_eq!(bitmap![], SimpleBitmap::new());
+    }
+}
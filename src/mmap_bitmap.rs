@@ -0,0 +1,198 @@
+use crate::SimpleBitmap;
+use crate::chunks::ChunkSource;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, Write};
+use std::iter::Enumerate;
+use std::path::Path;
+use std::slice::ChunksExact;
+
+/// Read-only bitmap that answers queries directly from a memory-mapped file, without loading it into memory.
+///
+/// The file holds the u32s of a [SimpleBitmap] in little-endian byte order, as written by [MmapBitmap::write]. Pages
+/// are loaded by the operating system on access, so even bitmaps larger than the available memory can be queried.
+pub struct MmapBitmap {
+    bytes: Mmap,
+}
+
+impl MmapBitmap {
+    /// Memory-maps the bitmap stored in the file at the given path.
+    ///
+    /// Fails with [io::ErrorKind::InvalidData] if the file length is not a multiple of four bytes.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the bitmap is alive, e.g. by another process. Otherwise, the
+    /// mapped memory changes underneath the bitmap, which is undefined behavior.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        // SAFETY: the caller guarantees that the file is not modified while it is mapped
+        let bytes = unsafe { Mmap::map(&file)? };
+
+        if bytes.len() % 4 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "file length of {} bytes is not a multiple of a u32",
+                    bytes.len()
+                ),
+            ));
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// Writes the u32s of the bitmap in the format expected by [MmapBitmap::open].
+    pub fn write(bitmap: &SimpleBitmap, mut writer: impl Write) -> io::Result<()> {
+        for bucket in bitmap.words() {
+            writer.write_all(&bucket.to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Returns whether a value is present.
+    pub fn get(&self, index: u32) -> bool {
+        let byte_index = (index / 32) as usize * 4;
+
+        self.bytes
+            .get(byte_index..byte_index + 4)
+            .map(read_u32)
+            .is_some_and(|bucket| (bucket >> (index & 0b11111)) & 0b1 == 1)
+    }
+
+    /// Returns the number of present values.
+    pub fn count_ones(&self) -> u64 {
+        self.buckets()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum()
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.buckets().all(|bucket| bucket == 0)
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> MmapOnes<'_> {
+        MmapOnes {
+            buckets: self.bytes.chunks_exact(4).enumerate(),
+            bucket: 0,
+            bucket_base: 0,
+        }
+    }
+
+    fn buckets(&self) -> impl Iterator<Item = u32> {
+        self.bytes.chunks_exact(4).map(read_u32)
+    }
+}
+
+impl ChunkSource for MmapBitmap {
+    fn word_len(&self) -> usize {
+        self.bytes.len() / 4
+    }
+
+    fn read_words(&self, start: usize, buffer: &mut [u32]) {
+        // only the pages of the requested u32s are touched
+        let bytes = self.bytes.get(start * 4..).unwrap_or_default();
+        let mut buckets = bytes.chunks_exact(4).map(read_u32);
+
+        for bucket in buffer {
+            *bucket = buckets.next().unwrap_or(0);
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().expect("chunks hold four bytes"))
+}
+
+/// Iterator over the indices of the values present in a [MmapBitmap], in ascending order.
+///
+/// Created by [MmapBitmap::iter].
+#[derive(Clone)]
+pub struct MmapOnes<'a> {
+    buckets: Enumerate<ChunksExact<'a, u8>>,
+    bucket: u32,
+    bucket_base: u32,
+}
+
+impl Iterator for MmapOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket == 0 {
+            let (u32_index_in_bits_vec, bytes) = self.buckets.next()?;
+            self.bucket = read_u32(bytes);
+            self.bucket_base = u32_index_in_bits_vec as u32 * 32;
+        }
+
+        let bit_index_in_u32 = self.bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.bucket &= self.bucket - 1;
+
+        Some(self.bucket_base + bit_index_in_u32)
+    }
+}
+
+impl<'a> IntoIterator for &'a MmapBitmap {
+    type Item = u32;
+    type IntoIter = MmapOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunks;
+    use std::{env, fs, process};
+
+    #[test]
+    fn it_answers_queries_from_the_mapped_file() {
+        // given
+        let path = env::temp_dir().join(format!("bitmaps-mmap-{}.bin", process::id()));
+        let bm: SimpleBitmap = [0, 31, 32, 1_000, 100_000].into_iter().collect();
+        MmapBitmap::write(&bm, File::create(&path).unwrap()).unwrap();
+
+        // when
+        // SAFETY: the file is owned by this test and not modified while it is mapped
+        let mapped = unsafe { MmapBitmap::open(&path) }.unwrap();
+
+        // then
+        assert!(mapped.get(31));
+        assert!(mapped.get(100_000));
+        assert!(!mapped.get(33));
+        assert!(!mapped.get(u32::MAX));
+        assert!(!mapped.is_empty());
+        assert_eq!(mapped.count_ones(), 5);
+        assert!(mapped.iter().eq(&bm));
+
+        let mut words = Vec::new();
+        chunks::for_each_chunk(&mapped, 3, |_, chunk| words.extend_from_slice(chunk));
+        assert_eq!(SimpleBitmap::from_words(words), bm);
+
+        drop(mapped);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn it_rejects_files_with_partial_u32s() {
+        // given
+        let path = env::temp_dir().join(format!("bitmaps-mmap-partial-{}.bin", process::id()));
+        fs::write(&path, [0xff; 6]).unwrap();
+
+        // when
+        // SAFETY: the file is owned by this test and not modified while it is mapped
+        let result = unsafe { MmapBitmap::open(&path) };
+
+        // then
+        assert_eq!(result.err().unwrap().kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(path).unwrap();
+    }
+}
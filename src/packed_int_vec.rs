@@ -0,0 +1,251 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// Vector of unsigned integers of a fixed width of 1 to 32 bits, e.g. 2 or 4 bit flags per slot, packed into u32s.
+///
+/// The integer at index `i` takes the bits `i * width` up to `(i + 1) * width` of the u32s, where bit `j` of the bits
+/// is bit `j % 32` of u32 `j / 32`, so integers can span two u32s.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackedIntVec {
+    bits: Vec<u32>,
+    width: u32,
+    len: usize,
+}
+
+impl PackedIntVec {
+    /// Creates an empty vector of integers of `width` bits.
+    ///
+    /// Panics if the width is not between 1 and 32.
+    pub fn new(width: u32) -> Self {
+        assert!(
+            (1..=32).contains(&width),
+            "width {width} is not between 1 and 32 bits"
+        );
+
+        Self {
+            bits: Vec::new(),
+            width,
+            len: 0,
+        }
+    }
+
+    /// Creates a vector of `len` integers of `width` bits, all of which are zero.
+    ///
+    /// Panics if the width is not between 1 and 32.
+    pub fn with_len(width: u32, len: usize) -> Self {
+        let mut ints = Self::new(width);
+        ints.bits = vec![0; Self::u32s_for(width, len)];
+        ints.len = len;
+
+        ints
+    }
+
+    /// Returns the number of bits of each integer.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the highest integer that fits into the width.
+    pub fn max_value(&self) -> u32 {
+        u32::MAX >> (32 - self.width)
+    }
+
+    /// Returns the number of integers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no integer.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the integer at the given index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<u32> {
+        (index < self.len).then(|| self.read(index))
+    }
+
+    /// Replaces the integer at the given index.
+    ///
+    /// Panics if the index is out of bounds or the value doesn't fit into the width.
+    pub fn set(&mut self, index: usize, value: u32) {
+        assert!(
+            index < self.len,
+            "index {index} is out of bounds for a vector of {} integers",
+            self.len
+        );
+        self.check_value(value);
+
+        self.write(index, value);
+    }
+
+    /// Appends an integer.
+    ///
+    /// Panics if the value doesn't fit into the width.
+    pub fn push(&mut self, value: u32) {
+        self.check_value(value);
+
+        self.len += 1;
+        self.bits.resize(Self::u32s_for(self.width, self.len), 0);
+        self.write(self.len - 1, value);
+    }
+
+    /// Returns an iterator over the integers, in the order of their indices.
+    pub fn iter(&self) -> PackedInts<'_> {
+        PackedInts {
+            ints: self,
+            indices: 0..self.len,
+        }
+    }
+
+    fn check_value(&self, value: u32) {
+        assert!(
+            value <= self.max_value(),
+            "value {value} doesn't fit into {} bits",
+            self.width
+        );
+    }
+
+    /// Returns the index of the u32 holding the lowest bit of the integer and the position of the bit within it.
+    fn position(&self, index: usize) -> (usize, u32) {
+        let bit_index = index as u64 * u64::from(self.width);
+
+        ((bit_index / 32) as usize, (bit_index & 0b11111) as u32)
+    }
+
+    fn read(&self, index: usize) -> u32 {
+        let (u32_index_in_bits_vec, bit_index_in_u32) = self.position(index);
+
+        // the integer can span two u32s, of which the first provides the lower and the second the upper bits
+        let lower = self.bits[u32_index_in_bits_vec] >> bit_index_in_u32;
+        let upper = if bit_index_in_u32 + self.width > 32 {
+            self.bits[u32_index_in_bits_vec + 1] << (32 - bit_index_in_u32)
+        } else {
+            0
+        };
+
+        (lower | upper) & self.max_value()
+    }
+
+    fn write(&mut self, index: usize, value: u32) {
+        let (u32_index_in_bits_vec, bit_index_in_u32) = self.position(index);
+        let mask = self.max_value();
+
+        let lower = &mut self.bits[u32_index_in_bits_vec];
+        *lower = (*lower & !(mask << bit_index_in_u32)) | (value << bit_index_in_u32);
+
+        if bit_index_in_u32 + self.width > 32 {
+            let shift = 32 - bit_index_in_u32;
+            let upper = &mut self.bits[u32_index_in_bits_vec + 1];
+            *upper = (*upper & !(mask >> shift)) | (value >> shift);
+        }
+    }
+
+    /// Number of u32s needed to hold `len` integers of `width` bits.
+    fn u32s_for(width: u32, len: usize) -> usize {
+        (len as u64 * u64::from(width)).div_ceil(32) as usize
+    }
+}
+
+impl FromIterator<u32> for PackedIntVec {
+    /// Collects the integers into a vector of the smallest width all of them fit into.
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let values: Vec<u32> = iter.into_iter().collect();
+        let max = values.iter().copied().max().unwrap_or(0);
+
+        let mut ints = PackedIntVec::new((32 - max.leading_zeros()).max(1));
+        for value in values {
+            ints.push(value);
+        }
+
+        ints
+    }
+}
+
+impl<'a> IntoIterator for &'a PackedIntVec {
+    type Item = u32;
+    type IntoIter = PackedInts<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the integers of a [PackedIntVec], in the order of their indices.
+///
+/// Created by [PackedIntVec::iter].
+#[derive(Clone)]
+pub struct PackedInts<'a> {
+    ints: &'a PackedIntVec,
+    indices: Range<usize>,
+}
+
+impl Iterator for PackedInts<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|index| self.ints.read(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for PackedInts<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.ints.read(index))
+    }
+}
+
+impl ExactSizeIterator for PackedInts<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_packs_integers_across_u32s() {
+        // given
+        let mut ints = PackedIntVec::with_len(5, 20);
+
+        // when
+        for index in 0..20 {
+            ints.set(index, (index as u32 * 7) % 32);
+        }
+        ints.set(6, 31);
+
+        // then
+        assert_eq!(ints.get(6), Some(31));
+        assert_eq!(ints.get(7), Some(17));
+        assert_eq!(ints.get(5), Some(3));
+        assert_eq!(ints.get(20), None);
+        assert_eq!(ints.iter().len(), 20);
+        assert_eq!(ints.bits.len(), 4);
+    }
+
+    #[test]
+    fn it_pushes_and_collects_integers() {
+        // given
+        let values = [0b11, 0, 0b10, 0b01, 0b11];
+
+        // when
+        let ints: PackedIntVec = values.into_iter().collect();
+        let mut full_width = PackedIntVec::new(32);
+        full_width.push(u32::MAX);
+        full_width.push(5);
+
+        // then
+        assert_eq!(ints.width(), 2);
+        assert_eq!(ints.iter().collect::<Vec<u32>>(), values);
+        assert_eq!(ints.iter().rev().nth(1), Some(0b01));
+        assert_eq!(full_width.iter().collect::<Vec<u32>>(), vec![u32::MAX, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 4 doesn't fit into 2 bits")]
+    fn it_rejects_values_wider_than_the_width() {
+        PackedIntVec::new(2).push(4);
+    }
+}
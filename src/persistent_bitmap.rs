@@ -0,0 +1,325 @@
+use crate::Ones;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::iter::Enumerate;
+use core::slice;
+
+/// Number of children of an inner node, and number of u32s of a leaf.
+const FAN_OUT: usize = 32;
+
+/// Shift of the index bits that select the u32 within a leaf.
+const LEAF_SHIFT: u32 = 5;
+
+/// Shift of the index bits that select the child of the root. The root covers the two highest bits, every level below
+/// covers five bits, and a leaf covers the lowest ten bits.
+const ROOT_SHIFT: u32 = 30;
+
+/// Immutable bitmap whose versions share all unchanged parts with each other.
+///
+/// The u32s are stored in the leaves of a trie whose nodes are reference counted. [PersistentBitmap::set] and
+/// [PersistentBitmap::clear] leave `self` untouched and return a new version, which copies only the nodes on the path to
+/// the modified u32. All other nodes are shared, so keeping many historical versions is cheap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PersistentBitmap {
+    // nodes without any set bit are never stored
+    root: Option<Arc<Node>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Inner([Option<Arc<Node>>; FAN_OUT]),
+    Leaf([u32; FAN_OUT]),
+}
+
+impl Node {
+    fn empty(shift: u32) -> Self {
+        if shift == LEAF_SHIFT {
+            Node::Leaf([0; FAN_OUT])
+        } else {
+            Node::Inner([const { None }; FAN_OUT])
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Inner(children) => children.iter().all(Option::is_none),
+            Node::Leaf(words) => words.iter().all(|bucket| *bucket == 0),
+        }
+    }
+
+    fn count_ones(&self) -> u64 {
+        match self {
+            Node::Inner(children) => children
+                .iter()
+                .flatten()
+                .map(|child| child.count_ones())
+                .sum(),
+            Node::Leaf(words) => words
+                .iter()
+                .map(|bucket| u64::from(bucket.count_ones()))
+                .sum(),
+        }
+    }
+}
+
+impl PersistentBitmap {
+    pub fn new() -> Self {
+        Self { root: None }
+    }
+
+    /// Returns a new version in which the value is present.
+    pub fn set(&self, index: u32) -> Self {
+        if self.get(index) {
+            return self.clone();
+        }
+
+        let mut bitmap = self.clone();
+        insert(&mut bitmap.root, index, ROOT_SHIFT);
+
+        bitmap
+    }
+
+    /// Returns a new version in which the value is absent.
+    pub fn clear(&self, index: u32) -> Self {
+        if !self.get(index) {
+            return self.clone();
+        }
+
+        let mut bitmap = self.clone();
+        remove(&mut bitmap.root, index, ROOT_SHIFT);
+
+        bitmap
+    }
+
+    /// Returns whether a value is present.
+    pub fn get(&self, index: u32) -> bool {
+        let mut node = self.root.as_deref();
+        let mut shift = ROOT_SHIFT;
+
+        while let Some(current) = node {
+            match current {
+                Node::Inner(children) => {
+                    node = children[child_index(index, shift)].as_deref();
+                    shift -= LEAF_SHIFT;
+                }
+                Node::Leaf(words) => {
+                    return (words[child_index(index, shift)] >> (index & 0b11111)) & 0b1 == 1;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Returns the number of present values.
+    pub fn count_ones(&self) -> u64 {
+        self.root.as_ref().map_or(0, |root| root.count_ones())
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        // empty nodes are removed eagerly
+        self.root.is_none()
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> PersistentOnes<'_> {
+        let mut iter = PersistentOnes {
+            nodes: Vec::new(),
+            leaf: None,
+        };
+        if let Some(root) = &self.root {
+            iter.descend(root, 0, ROOT_SHIFT);
+        }
+
+        iter
+    }
+}
+
+fn child_index(index: u32, shift: u32) -> usize {
+    ((index >> shift) as usize) % FAN_OUT
+}
+
+/// Sets the bit in the subtree of `slot`, copying every node on the way that is shared with other versions.
+fn insert(slot: &mut Option<Arc<Node>>, index: u32, shift: u32) {
+    let node = Arc::make_mut(slot.get_or_insert_with(|| Arc::new(Node::empty(shift))));
+
+    match node {
+        Node::Inner(children) => insert(
+            &mut children[child_index(index, shift)],
+            index,
+            shift - LEAF_SHIFT,
+        ),
+        Node::Leaf(words) => words[child_index(index, shift)] |= 0b1 << (index & 0b11111),
+    }
+}
+
+/// Clears the bit in the subtree of `slot` like [insert], dropping nodes that become empty.
+fn remove(slot: &mut Option<Arc<Node>>, index: u32, shift: u32) {
+    let Some(node) = slot else {
+        return;
+    };
+    let node = Arc::make_mut(node);
+
+    match node {
+        Node::Inner(children) => remove(
+            &mut children[child_index(index, shift)],
+            index,
+            shift - LEAF_SHIFT,
+        ),
+        Node::Leaf(words) => words[child_index(index, shift)] &= !(0b1 << (index & 0b11111)),
+    }
+
+    // nodes without any set bit are never stored
+    if node.is_empty() {
+        *slot = None;
+    }
+}
+
+impl FromIterator<u32> for PersistentBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        // no other version exists yet, so the nodes are modified in place instead of being copied
+        let mut bitmap = PersistentBitmap::new();
+        for index in iter {
+            insert(&mut bitmap.root, index, ROOT_SHIFT);
+        }
+
+        bitmap
+    }
+}
+
+type Children<'a> = Enumerate<slice::Iter<'a, Option<Arc<Node>>>>;
+
+/// Iterator over the indices of the values present in a [PersistentBitmap], in ascending order.
+///
+/// Created by [PersistentBitmap::iter].
+#[derive(Clone)]
+pub struct PersistentOnes<'a> {
+    // the children still to visit of every inner node on the path to the current leaf, with the first index covered
+    // by the node and the shift selecting its children
+    nodes: Vec<(Children<'a>, u32, u32)>,
+    leaf: Option<(u32, Ones<'a>)>,
+}
+
+impl<'a> PersistentOnes<'a> {
+    fn descend(&mut self, node: &'a Node, base: u32, shift: u32) {
+        match node {
+            Node::Inner(children) => self.nodes.push((children.iter().enumerate(), base, shift)),
+            Node::Leaf(words) => self.leaf = Some((base, Ones::new(words))),
+        }
+    }
+}
+
+impl Iterator for PersistentOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((base, ones)) = &mut self.leaf
+                && let Some(index_in_leaf) = ones.next()
+            {
+                return Some(*base + index_in_leaf);
+            }
+            self.leaf = None;
+
+            let (children, base, shift) = self.nodes.last_mut()?;
+            let (base, shift) = (*base, *shift);
+
+            match children.next() {
+                Some((child_index, Some(child))) => {
+                    self.descend(
+                        child,
+                        base | ((child_index as u32) << shift),
+                        shift - LEAF_SHIFT,
+                    );
+                }
+                Some((_, None)) => {}
+                None => {
+                    self.nodes.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a PersistentBitmap {
+    type Item = u32;
+    type IntoIter = PersistentOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_earlier_versions_untouched() {
+        // given
+        let empty = PersistentBitmap::new();
+
+        // when
+        let first = empty.set(3);
+        let second = first.set(1_000_000).set(u32::MAX);
+        let third = second.clear(3);
+
+        // then
+        assert!(empty.is_empty());
+        assert_eq!(first.iter().collect::<Vec<u32>>(), vec![3]);
+        assert_eq!(
+            second.iter().collect::<Vec<u32>>(),
+            vec![3, 1_000_000, u32::MAX]
+        );
+        assert_eq!(
+            third.iter().collect::<Vec<u32>>(),
+            vec![1_000_000, u32::MAX]
+        );
+        assert_eq!(third.count_ones(), 2);
+        assert!(second.get(3));
+        assert!(!third.get(3));
+        assert_eq!(third.clear(1_000_000).clear(u32::MAX), empty);
+    }
+
+    #[test]
+    fn it_shares_unchanged_nodes_between_versions() {
+        // given
+        let original: PersistentBitmap = (0..10_000).step_by(7).collect();
+
+        // when
+        let modified = original.set(u32::MAX);
+
+        // then
+        let (Some(Node::Inner(original_children)), Some(Node::Inner(modified_children))) =
+            (original.root.as_deref(), modified.root.as_deref())
+        else {
+            panic!("roots are inner nodes");
+        };
+        assert!(Arc::ptr_eq(
+            original_children[0].as_ref().unwrap(),
+            modified_children[0].as_ref().unwrap()
+        ));
+        assert!(
+            original
+                .iter()
+                .eq(modified.iter().take_while(|index| *index != u32::MAX))
+        );
+    }
+
+    #[test]
+    fn it_iterates_in_ascending_order() {
+        // given
+        let indices: Vec<u32> = (0..200_000)
+            .filter(|index| index % 3 == 0)
+            .chain([1 << 30, 3 << 30, u32::MAX])
+            .collect();
+
+        // when
+        let bm: PersistentBitmap = indices.iter().rev().copied().collect();
+
+        // then
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), indices);
+        assert_eq!(bm.count_ones(), indices.len() as u64);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::SimpleBitmap;
+use crate::simple_bitmap::select_in_u32;
+use alloc::vec::Vec;
+
+/// Number of u32s covered by one block of precomputed ranks.
+const U32S_PER_BLOCK: usize = 16;
+
+/// Wrapper around a [SimpleBitmap] that precomputes the cumulative number of values per block of u32s, so that `rank`
+/// runs in constant time and `select` in logarithmic time.
+#[derive(Clone, Debug)]
+pub struct RankSelectBitmap {
+    bitmap: SimpleBitmap,
+    // number of values in all blocks before the block at the same position
+    block_ranks: Vec<u64>,
+    count_ones: u64,
+}
+
+impl RankSelectBitmap {
+    pub fn new(bitmap: SimpleBitmap) -> Self {
+        let mut rank_select_bitmap = Self {
+            bitmap,
+            block_ranks: Vec::new(),
+            count_ones: 0,
+        };
+        rank_select_bitmap.rebuild();
+
+        rank_select_bitmap
+    }
+
+    /// Returns the wrapped bitmap.
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Unwraps the bitmap, dropping the precomputed ranks.
+    pub fn into_inner(self) -> SimpleBitmap {
+        self.bitmap
+    }
+
+    /// Mutates the wrapped bitmap and recomputes the ranks afterwards.
+    pub fn modify<F: FnOnce(&mut SimpleBitmap)>(&mut self, f: F) {
+        f(&mut self.bitmap);
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        let words = self.bitmap.words();
+
+        self.block_ranks.clear();
+        self.block_ranks
+            .reserve(words.len().div_ceil(U32S_PER_BLOCK));
+
+        let mut count_ones = 0;
+        for block in words.chunks(U32S_PER_BLOCK) {
+            self.block_ranks.push(count_ones);
+            count_ones += block
+                .iter()
+                .map(|bucket| u64::from(bucket.count_ones()))
+                .sum::<u64>();
+        }
+
+        self.count_ones = count_ones;
+    }
+
+    /// Counts the values present in the wrapped bitmap.
+    pub fn count_ones(&self) -> u64 {
+        self.count_ones
+    }
+
+    /// Counts the values present strictly below the given index.
+    pub fn rank(&self, index: u32) -> u64 {
+        let words = self.bitmap.words();
+        let u32_index_in_bits_vec = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        if u32_index_in_bits_vec >= words.len() {
+            return self.count_ones;
+        }
+
+        // at most the u32s of a single block have to be counted on top of the precomputed rank
+        let block_index = u32_index_in_bits_vec / U32S_PER_BLOCK;
+        let block_start = block_index * U32S_PER_BLOCK;
+
+        let full_u32s: u64 = words[block_start..u32_index_in_bits_vec]
+            .iter()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum();
+        let partial_u32 = words[u32_index_in_bits_vec] & !(u32::MAX << bit_index_in_u32);
+
+        self.block_ranks[block_index] + full_u32s + u64::from(partial_u32.count_ones())
+    }
+
+    /// Returns the index of the `k`-th present value, counting from zero.
+    pub fn select(&self, k: u64) -> Option<u32> {
+        if k >= self.count_ones {
+            return None;
+        }
+
+        // the block holding the k-th value is the last one whose rank doesn't exceed k
+        let block_index = self
+            .block_ranks
+            .partition_point(|block_rank| *block_rank <= k)
+            - 1;
+        let block_start = block_index * U32S_PER_BLOCK;
+
+        let mut remaining = k - self.block_ranks[block_index];
+
+        for (offset, bucket) in self.bitmap.words()[block_start..].iter().enumerate() {
+            let ones = u64::from(bucket.count_ones());
+
+            if remaining < ones {
+                let u32_index_in_bits_vec = (block_start + offset) as u32;
+                return Some(u32_index_in_bits_vec * 32 + select_in_u32(*bucket, remaining as u32));
+            }
+
+            remaining -= ones;
+        }
+
+        None
+    }
+}
+
+impl From<SimpleBitmap> for RankSelectBitmap {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        Self::new(bitmap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+
+    #[test]
+    fn it_ranks_and_selects_like_the_wrapped_bitmap() {
+        // given
+        let bm: SimpleBitmap = (0..5000)
+            .filter(|index| index % 3 == 0 || index % 7 == 0)
+            .collect();
+
+        // when
+        let rank_select_bm = RankSelectBitmap::new(bm.clone());
+
+        // then
+        assert_eq!(rank_select_bm.count_ones(), bm.count_ones());
+        for index in (0..5100).step_by(13) {
+            assert_eq!(rank_select_bm.rank(index), bm.rank(index));
+        }
+        for k in 0..bm.count_ones() + 2 {
+            assert_eq!(rank_select_bm.select(k), bm.select(k));
+        }
+    }
+
+    #[test]
+    fn it_rebuilds_ranks_after_modifications() {
+        // given
+        let mut rank_select_bm = RankSelectBitmap::new(SimpleBitmap::new());
+        assert_eq!(rank_select_bm.select(0), None);
+
+        // when
+        rank_select_bm.modify(|bm| {
+            bm.set(10);
+            bm.set(1000);
+        });
+
+        // then
+        assert_eq!(rank_select_bm.rank(1000), 1);
+        assert_eq!(rank_select_bm.rank(1001), 2);
+        assert_eq!(rank_select_bm.select(1), Some(1000));
+    }
+}
@@ -0,0 +1,473 @@
+use crate::Bitmap;
+use alloc::vec::Vec;
+use container::{Container, ContainerIter};
+use core::fmt::{Debug, Formatter};
+use core::ops::{BitAnd, BitOr, BitXor, Range, Sub};
+use core::slice;
+
+mod container;
+mod portable;
+
+/// Compressed implementation of a [Bitmap] following the
+/// [Roaring](https://roaringbitmap.org/) layout.
+///
+/// Values are partitioned by their upper 16 bits into containers, which each store the lower 16 bits either as a
+/// sorted array (sparse), a bitset (dense) or a list of runs (contiguous ranges). Memory therefore grows with the
+/// number of values instead of with the highest index.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RoaringBitmap {
+    // sorted by key, containers are never empty
+    containers: Vec<(u16, Container)>,
+}
+
+impl RoaringBitmap {
+    pub fn new() -> Self {
+        Self {
+            containers: Vec::new(),
+        }
+    }
+
+    /// Sets the presence of all values in the given range.
+    pub fn set_range(&mut self, range: Range<u32>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first_key = split(range.start).0;
+        let last_key = split(range.end - 1).0;
+
+        for key in first_key..=last_key {
+            let start = if key == first_key {
+                split(range.start).1
+            } else {
+                0
+            };
+            let end = if key == last_key {
+                split(range.end - 1).1
+            } else {
+                u16::MAX
+            };
+
+            self.container_mut(key).insert_range(start..=end);
+        }
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        // empty containers are removed eagerly
+        self.containers.is_empty()
+    }
+
+    /// Returns the lowest index at which a value is present.
+    pub fn first_set(&self) -> Option<u32> {
+        let (key, container) = self.containers.first()?;
+
+        container.min().map(|low| join(*key, low))
+    }
+
+    /// Returns the highest index at which a value is present.
+    pub fn last_set(&self) -> Option<u32> {
+        let (key, container) = self.containers.last()?;
+
+        container.max().map(|low| join(*key, low))
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> RoaringOnes<'_> {
+        RoaringOnes {
+            containers: self.containers.iter(),
+            current: None,
+        }
+    }
+
+    /// Switches every container to the representation with the smallest serialized size, e.g. long runs of values to
+    /// a list of runs, like `runOptimize` of CRoaring. Returns `true` if any container stores runs afterwards.
+    pub fn optimize(&mut self) -> bool {
+        // every container has to be optimized, so the loop must not stop at the first run container
+        self.containers
+            .iter_mut()
+            .fold(false, |has_runs, (_, container)| {
+                container.optimize() | has_runs
+            })
+    }
+
+    fn container(&self, key: u16) -> Option<&Container> {
+        self.containers
+            .binary_search_by_key(&key, |(container_key, _)| *container_key)
+            .ok()
+            .map(|position| &self.containers[position].1)
+    }
+
+    /// Returns the container for the given key, inserting an empty one if there is none yet.
+    fn container_mut(&mut self, key: u16) -> &mut Container {
+        let position = match self
+            .containers
+            .binary_search_by_key(&key, |(container_key, _)| *container_key)
+        {
+            Ok(position) => position,
+            Err(position) => {
+                self.containers.insert(position, (key, Container::new()));
+                position
+            }
+        };
+
+        &mut self.containers[position].1
+    }
+}
+
+/// Splits an index into the key of its container and the value within the container.
+fn split(index: u32) -> (u16, u16) {
+    ((index >> 16) as u16, index as u16)
+}
+
+fn join(key: u16, low: u16) -> u32 {
+    (u32::from(key) << 16) | u32::from(low)
+}
+
+impl Bitmap for RoaringBitmap {
+    fn set(&mut self, index: u32) {
+        let (key, low) = split(index);
+
+        self.container_mut(key).insert(low);
+    }
+
+    fn clear(&mut self, index: u32) {
+        let (key, low) = split(index);
+
+        if let Ok(position) = self
+            .containers
+            .binary_search_by_key(&key, |(container_key, _)| *container_key)
+        {
+            let container = &mut self.containers[position].1;
+            container.remove(low);
+
+            // containers are never kept empty
+            if container.is_empty() {
+                self.containers.remove(position);
+            }
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        let (key, low) = split(index);
+
+        self.container(key)
+            .is_some_and(|container| container.contains(low))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.containers
+            .iter()
+            .map(|(_, container)| u64::from(container.len()))
+            .sum()
+    }
+}
+
+/// Merges the containers of both bitmaps by key. Containers only present on one side are kept if requested, containers
+/// present on both sides are combined with `op`. Empty results are dropped.
+fn merge(
+    left: RoaringBitmap,
+    right: RoaringBitmap,
+    op: impl Fn(&Container, &Container) -> Container,
+    keep_left: bool,
+    keep_right: bool,
+) -> RoaringBitmap {
+    let mut containers = Vec::with_capacity(left.containers.len().max(right.containers.len()));
+    let mut left = left.containers.into_iter().peekable();
+    let mut right = right.containers.into_iter().peekable();
+
+    loop {
+        match (left.peek(), right.peek()) {
+            (Some((left_key, _)), Some((right_key, _))) if left_key < right_key => {
+                let entry = left.next().expect("entry was peeked");
+                if keep_left {
+                    containers.push(entry);
+                }
+            }
+            (Some((left_key, _)), Some((right_key, _))) if left_key > right_key => {
+                let entry = right.next().expect("entry was peeked");
+                if keep_right {
+                    containers.push(entry);
+                }
+            }
+            (Some(_), Some(_)) => {
+                let (key, left_container) = left.next().expect("entry was peeked");
+                let (_, right_container) = right.next().expect("entry was peeked");
+
+                let container = op(&left_container, &right_container);
+                if !container.is_empty() {
+                    containers.push((key, container));
+                }
+            }
+            (Some(_), None) => {
+                if keep_left {
+                    containers.extend(left);
+                }
+                break;
+            }
+            (None, Some(_)) => {
+                if keep_right {
+                    containers.extend(right);
+                }
+                break;
+            }
+            (None, None) => break,
+        }
+    }
+
+    RoaringBitmap { containers }
+}
+
+impl BitOr for RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        merge(self, rhs, Container::union, true, true)
+    }
+}
+
+impl BitAnd for RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        merge(self, rhs, Container::intersection, false, false)
+    }
+}
+
+impl BitXor for RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        merge(self, rhs, Container::symmetric_difference, true, true)
+    }
+}
+
+impl Sub for RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        merge(self, rhs, Container::difference, true, false)
+    }
+}
+
+impl FromIterator<u32> for RoaringBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bitmap = RoaringBitmap::new();
+        bitmap.extend(iter);
+
+        bitmap
+    }
+}
+
+impl Extend<u32> for RoaringBitmap {
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for index in iter {
+            self.set(index);
+        }
+    }
+}
+
+/// Iterator over the indices of the values present in a [RoaringBitmap], in ascending order.
+///
+/// Created by [RoaringBitmap::iter].
+#[derive(Clone)]
+pub struct RoaringOnes<'a> {
+    containers: slice::Iter<'a, (u16, Container)>,
+    current: Option<(u16, ContainerIter<'a>)>,
+}
+
+impl Iterator for RoaringOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.current
+                && let Some(low) = values.next()
+            {
+                return Some(join(*key, low));
+            }
+
+            let (key, container) = self.containers.next()?;
+            self.current = Some((*key, container.iter()));
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a RoaringBitmap {
+    type Item = u32;
+    type IntoIter = RoaringOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Debug for RoaringBitmap {
+    /// Shows summary statistics like [SimpleBitmap](crate::SimpleBitmap). The alternate form `{:#?}` additionally lists
+    /// the representation and size of every container by its upper 16 bits.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("RoaringBitmap");
+        debug
+            .field("count", &self.count_ones())
+            .field("min", &self.first_set())
+            .field("max", &self.last_set())
+            .field("containers", &self.containers.len());
+
+        if alternate {
+            debug.field("by_key", &DebugContainers(&self.containers));
+        }
+
+        debug.finish()
+    }
+}
+
+/// Lists the containers by their key.
+struct DebugContainers<'a>(&'a [(u16, Container)]);
+
+impl Debug for DebugContainers<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(key, container)| (key, container)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_combines_like_simple_bitmaps;
+
+    #[test]
+    fn it_sets_and_gets_bits() {
+        // given
+        let mut bm = RoaringBitmap::new();
+
+        // when
+        bm.set(31);
+        bm.set(65_536);
+        bm.set(u32::MAX);
+        bm.clear(31);
+
+        // then
+        assert!(!bm.get(0));
+        assert!(!bm.get(31));
+        assert!(bm.get(65_536));
+        assert!(bm.get(u32::MAX));
+        assert_eq!(bm.count_ones(), 2);
+        assert_eq!(bm.containers.len(), 2);
+        assert_eq!(bm.first_set(), Some(65_536));
+        assert_eq!(bm.last_set(), Some(u32::MAX));
+    }
+
+    #[test]
+    fn it_removes_empty_containers() {
+        // given
+        let mut bm = RoaringBitmap::new();
+        bm.set(3);
+
+        // when
+        bm.clear(3);
+        bm.clear(1_000_000);
+
+        // then
+        assert!(bm.is_empty());
+        assert_eq!(bm, RoaringBitmap::new());
+    }
+
+    #[test]
+    fn it_iterates_over_dense_and_sparse_containers() {
+        // given
+        let dense = (0..70_000).filter(|index| index % 3 == 0);
+        let sparse = [200_000, 5_000_000, u32::MAX];
+
+        // when
+        let bm: RoaringBitmap = dense.clone().chain(sparse).collect();
+
+        // then
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            dense.chain(sparse).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn it_sets_ranges() {
+        // given
+        let mut bm = RoaringBitmap::new();
+
+        // when
+        bm.set_range(100..200_000);
+
+        // then
+        assert_eq!(bm.count_ones(), 199_900);
+        assert!(!bm.get(99));
+        assert!(bm.get(100));
+        assert!(bm.get(199_999));
+        assert!(!bm.get(200_000));
+        assert!(
+            bm.containers
+                .iter()
+                .all(|(_, container)| matches!(container, Container::Run(_)))
+        );
+    }
+
+    #[test]
+    fn it_optimizes_containers_into_runs() {
+        // given
+        let mut bm: RoaringBitmap = (0..100_000).chain([200_000, 200_002]).collect();
+        let size_before = bm.to_portable_bytes().len();
+
+        // when
+        let has_runs = bm.optimize();
+
+        // then
+        assert!(has_runs);
+        assert!(bm.to_portable_bytes().len() < size_before);
+        assert!(matches!(bm.containers[0].1, Container::Run(_)));
+        assert!(matches!(bm.containers[2].1, Container::Array(_)));
+        assert_eq!(bm.count_ones(), 100_002);
+        assert!(bm.get(200_002));
+    }
+
+    #[test]
+    fn it_combines_bitmaps_like_simple_bitmaps() {
+        // given
+        let left: Vec<u32> = (0..100_000)
+            .filter(|index| index % 3 == 0)
+            .chain([1_000_000])
+            .collect();
+        let right: Vec<u32> = (50_000..300_000).filter(|index| index % 5 == 0).collect();
+
+        // then
+        assert_combines_like_simple_bitmaps::<RoaringBitmap>(&left, &right);
+    }
+
+    #[test]
+    fn it_summarizes_containers_in_debug_output() {
+        // given
+        let mut bm: RoaringBitmap = (0..5_000)
+            .chain([70_000])
+            .chain((131_072..151_072).step_by(2))
+            .collect();
+        bm.optimize();
+
+        // then
+        assert_eq!(
+            format!("{bm:?}"),
+            "RoaringBitmap { count: 15001, min: Some(0), max: Some(151070), containers: 3 }"
+        );
+        assert_eq!(
+            format!("{:?}", bm.containers[0].1),
+            "Run { len: 5000, runs: 1 }"
+        );
+        assert_eq!(format!("{:?}", bm.containers[1].1), "Array { len: 1 }");
+        assert_eq!(format!("{:?}", bm.containers[2].1), "Bitset { len: 10000 }");
+        assert!(format!("{bm:#?}").contains("1: Array"));
+    }
+}
@@ -0,0 +1,848 @@
+use crate::error::{ByteReader, DecodeError};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::ops::RangeInclusive;
+use core::{iter, slice};
+
+/// Arrays with more values than this are stored as bitsets instead, as both take 8 KiB at this point.
+pub(crate) const ARRAY_MAX_LEN: usize = 4096;
+
+/// Number of u64s needed for one bit per value of a container.
+pub(crate) const BITSET_WORDS: usize = 1024;
+
+/// Holds the lower 16 bits of all values of a [RoaringBitmap](crate::RoaringBitmap) that share the same upper 16 bits.
+#[derive(Clone)]
+pub(crate) enum Container {
+    /// Sorted values, used for sparse containers.
+    Array(Vec<u16>),
+    /// One bit per value, used for dense containers.
+    Bitset(Bitset),
+    /// Sorted, non-overlapping and non-adjacent runs of values, stored as `(start, length - 1)` pairs.
+    Run(Vec<(u16, u16)>),
+}
+
+/// Fixed-size bitset of 65536 bits that keeps track of its number of set bits.
+#[derive(Clone)]
+pub(crate) struct Bitset {
+    words: Box<[u64; BITSET_WORDS]>,
+    len: u32,
+}
+
+impl Bitset {
+    fn new() -> Self {
+        Self {
+            words: Box::new([0; BITSET_WORDS]),
+            len: 0,
+        }
+    }
+
+    fn from_words(words: Box<[u64; BITSET_WORDS]>) -> Self {
+        let len = words.iter().map(|word| word.count_ones()).sum();
+
+        Self { words, len }
+    }
+
+    fn contains(&self, value: u16) -> bool {
+        (self.words[usize::from(value / 64)] >> (value % 64)) & 0b1 == 1
+    }
+
+    fn insert(&mut self, value: u16) -> bool {
+        let word = &mut self.words[usize::from(value / 64)];
+        let mask = 0b1 << (value % 64);
+
+        let is_new = *word & mask == 0;
+        *word |= mask;
+        self.len += u32::from(is_new);
+
+        is_new
+    }
+
+    fn remove(&mut self, value: u16) -> bool {
+        let word = &mut self.words[usize::from(value / 64)];
+        let mask = 0b1 << (value % 64);
+
+        let was_present = *word & mask != 0;
+        *word &= !mask;
+        self.len -= u32::from(was_present);
+
+        was_present
+    }
+
+    fn insert_range(&mut self, range: RangeInclusive<u16>) {
+        let (start, end) = (usize::from(*range.start()), usize::from(*range.end()));
+        if start > end {
+            return;
+        }
+
+        // all u64s between the first and last one are fully covered, only those two need a partial mask
+        for word_index in start / 64..=end / 64 {
+            let lowest_bit = if word_index == start / 64 {
+                start % 64
+            } else {
+                0
+            };
+            let highest_bit = if word_index == end / 64 { end % 64 } else { 63 };
+            let mask = (u64::MAX << lowest_bit) & (u64::MAX >> (63 - highest_bit));
+
+            let word = &mut self.words[word_index];
+            self.len += (mask & !*word).count_ones();
+            *word |= mask;
+        }
+    }
+}
+
+impl Container {
+    pub(crate) fn new() -> Self {
+        Container::Array(Vec::new())
+    }
+
+    /// Builds the smaller of an array and a bitset container from sorted, deduplicated values.
+    fn from_sorted_values(values: Vec<u16>) -> Self {
+        if values.len() <= ARRAY_MAX_LEN {
+            Container::Array(values)
+        } else {
+            let mut bitset = Bitset::new();
+            for value in values {
+                bitset.insert(value);
+            }
+            Container::Bitset(bitset)
+        }
+    }
+
+    /// Builds the smaller of an array and a bitset container from a bitset.
+    pub(crate) fn from_bitset(bitset: Bitset) -> Self {
+        if bitset.len as usize <= ARRAY_MAX_LEN {
+            Container::Array(iter_bitset(&bitset.words).collect())
+        } else {
+            Container::Bitset(bitset)
+        }
+    }
+
+    pub(crate) fn len(&self) -> u32 {
+        match self {
+            Container::Array(values) => values.len() as u32,
+            Container::Bitset(bitset) => bitset.len,
+            Container::Run(runs) => runs.iter().map(|(_, length)| u32::from(*length) + 1).sum(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn contains(&self, value: u16) -> bool {
+        match self {
+            Container::Array(values) => values.binary_search(&value).is_ok(),
+            Container::Bitset(bitset) => bitset.contains(value),
+            Container::Run(runs) => {
+                // the only run that can contain the value is the last one starting at or before it
+                let candidates = runs.partition_point(|(start, _)| *start <= value);
+
+                candidates > 0 && {
+                    let (start, length) = runs[candidates - 1];
+                    u32::from(value) <= u32::from(start) + u32::from(length)
+                }
+            }
+        }
+    }
+
+    /// Adds the value and returns whether it was absent before.
+    pub(crate) fn insert(&mut self, value: u16) -> bool {
+        match self {
+            Container::Array(values) => {
+                let Err(position) = values.binary_search(&value) else {
+                    return false;
+                };
+                values.insert(position, value);
+
+                if values.len() > ARRAY_MAX_LEN {
+                    *self = Container::from_sorted_values(core::mem::take(values));
+                }
+
+                true
+            }
+            Container::Bitset(bitset) => bitset.insert(value),
+            Container::Run(runs) => {
+                // the only runs the value can belong or be adjacent to are the last one starting at or before it and
+                // the one after that
+                let position = runs.partition_point(|(start, _)| *start <= value);
+                let previous_end = position
+                    .checked_sub(1)
+                    .map(|previous| u32::from(runs[previous].0) + u32::from(runs[previous].1));
+
+                if previous_end.is_some_and(|end| u32::from(value) <= end) {
+                    return false;
+                }
+
+                let extends_previous = previous_end.is_some_and(|end| end + 1 == u32::from(value));
+                let extends_next = runs
+                    .get(position)
+                    .is_some_and(|(start, _)| u32::from(*start) == u32::from(value) + 1);
+
+                match (extends_previous, extends_next) {
+                    (true, true) => {
+                        let (_, next_length) = runs.remove(position);
+                        let (previous_start, previous_length) = &mut runs[position - 1];
+                        *previous_length = value - *previous_start + 1 + next_length;
+                    }
+                    (true, false) => runs[position - 1].1 += 1,
+                    (false, true) => {
+                        let (start, length) = &mut runs[position];
+                        *start = value;
+                        *length += 1;
+                    }
+                    (false, false) => runs.insert(position, (value, 0)),
+                }
+
+                // a new run may make the runs larger than an array or bitset
+                self.optimize();
+                true
+            }
+        }
+    }
+
+    /// Removes the value and returns whether it was present before.
+    pub(crate) fn remove(&mut self, value: u16) -> bool {
+        match self {
+            Container::Array(values) => {
+                let Ok(position) = values.binary_search(&value) else {
+                    return false;
+                };
+                values.remove(position);
+
+                true
+            }
+            Container::Bitset(bitset) => {
+                let was_present = bitset.remove(value);
+
+                if bitset.len as usize <= ARRAY_MAX_LEN {
+                    *self = Container::Array(iter_bitset(&bitset.words).collect());
+                }
+
+                was_present
+            }
+            Container::Run(runs) => {
+                // the only run that can contain the value is the last one starting at or before it
+                let position = runs.partition_point(|(start, _)| *start <= value);
+                let Some(previous) = position.checked_sub(1) else {
+                    return false;
+                };
+                let (start, length) = runs[previous];
+                let end = start + length;
+
+                if value > end {
+                    return false;
+                }
+
+                if start == end {
+                    runs.remove(previous);
+                } else if value == start {
+                    runs[previous] = (start + 1, length - 1);
+                } else if value == end {
+                    runs[previous].1 -= 1;
+                } else {
+                    // removing a value from the middle splits the run in two
+                    runs[previous].1 = value - start - 1;
+                    runs.insert(position, (value + 1, end - value - 1));
+                }
+
+                // splitting a run or removing values may make the runs larger than an array or bitset
+                self.optimize();
+                true
+            }
+        }
+    }
+
+    /// Adds all values of the range and switches to the smallest representation afterwards.
+    pub(crate) fn insert_range(&mut self, range: RangeInclusive<u16>) {
+        let mut bitset = self.to_bitset();
+        bitset.insert_range(range);
+
+        *self = Container::from_bitset(bitset);
+        self.optimize();
+    }
+
+    pub(crate) fn min(&self) -> Option<u16> {
+        self.iter().next()
+    }
+
+    pub(crate) fn max(&self) -> Option<u16> {
+        match self {
+            Container::Array(values) => values.last().copied(),
+            Container::Bitset(bitset) => bitset
+                .words
+                .iter()
+                .enumerate()
+                .rfind(|(_, word)| **word != 0)
+                .map(|(word_index, word)| {
+                    (word_index * 64 + 63 - word.leading_zeros() as usize) as u16
+                }),
+            Container::Run(runs) => runs.last().map(|(start, length)| start + length),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(values) => ContainerIter::Array(values.iter()),
+            Container::Bitset(bitset) => ContainerIter::Bitset(iter_bitset(&bitset.words)),
+            Container::Run(runs) => ContainerIter::Run(iter_runs(runs)),
+        }
+    }
+
+    /// Number of runs of consecutive values, independent of the representation.
+    pub(crate) fn number_of_runs(&self) -> usize {
+        match self {
+            Container::Run(runs) => runs.len(),
+            _ => {
+                let mut previous: Option<u16> = None;
+
+                self.iter()
+                    .filter(|value| {
+                        let starts_run = previous.is_none_or(|previous| previous + 1 != *value);
+                        previous = Some(*value);
+                        starts_run
+                    })
+                    .count()
+            }
+        }
+    }
+
+    /// Switches to the representation with the smallest serialized size and returns whether a run container is used
+    /// afterwards.
+    pub(crate) fn optimize(&mut self) -> bool {
+        let len = self.len() as usize;
+        let number_of_runs = self.number_of_runs();
+
+        let array_or_bitset_size = if len <= ARRAY_MAX_LEN {
+            2 * len
+        } else {
+            8 * BITSET_WORDS
+        };
+        let run_size = 2 + 4 * number_of_runs;
+
+        if run_size < array_or_bitset_size {
+            if !matches!(self, Container::Run(_)) {
+                *self = Container::Run(collect_runs(self.iter()));
+            }
+            true
+        } else {
+            if matches!(self, Container::Run(_)) {
+                *self = Container::from_bitset(self.to_bitset());
+            }
+            false
+        }
+    }
+
+    /// Number of bytes the values of the container take in the portable format.
+    pub(crate) fn portable_size(&self) -> usize {
+        match self {
+            Container::Array(values) => 2 * values.len(),
+            Container::Bitset(_) => 8 * BITSET_WORDS,
+            Container::Run(runs) => 2 + 4 * runs.len(),
+        }
+    }
+
+    /// Appends the values of the container in the portable format.
+    ///
+    /// The format tells arrays and bitsets apart by their length, which matches the representation chosen here, as
+    /// arrays never hold more than [ARRAY_MAX_LEN] values and bitsets never less.
+    pub(crate) fn write_portable(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Container::Array(values) => {
+                for value in values {
+                    bytes.extend(value.to_le_bytes());
+                }
+            }
+            Container::Bitset(bitset) => {
+                for word in bitset.words.iter() {
+                    bytes.extend(word.to_le_bytes());
+                }
+            }
+            Container::Run(runs) => {
+                bytes.extend((runs.len() as u16).to_le_bytes());
+                for (start, length) in runs {
+                    bytes.extend(start.to_le_bytes());
+                    bytes.extend(length.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    /// Reads the values of a container with `len` values in the portable format.
+    pub(crate) fn read_portable(
+        reader: &mut ByteReader<'_>,
+        len: u32,
+        is_run: bool,
+    ) -> Result<Self, DecodeError> {
+        let container = if is_run {
+            let number_of_runs = reader.read_u16()?;
+            let mut runs: Vec<(u16, u16)> = Vec::with_capacity(usize::from(number_of_runs));
+
+            for _ in 0..number_of_runs {
+                let start = reader.read_u16()?;
+                let length = reader.read_u16()?;
+                let end = u32::from(start) + u32::from(length);
+
+                if end > u32::from(u16::MAX) {
+                    return Err(DecodeError::Invalid("run exceeds its container"));
+                }
+
+                match runs.last_mut() {
+                    Some((last_start, last_length))
+                        if u32::from(start) <= u32::from(*last_start) + u32::from(*last_length) =>
+                    {
+                        return Err(DecodeError::Invalid("runs are not sorted"));
+                    }
+                    // other implementations may write adjacent runs, which are merged to keep them non-adjacent
+                    Some((last_start, last_length))
+                        if u32::from(start)
+                            == u32::from(*last_start) + u32::from(*last_length) + 1 =>
+                    {
+                        *last_length = (end - u32::from(*last_start)) as u16;
+                    }
+                    _ => runs.push((start, length)),
+                }
+            }
+
+            Container::Run(runs)
+        } else if len as usize <= ARRAY_MAX_LEN {
+            let values = (0..len)
+                .map(|_| reader.read_u16())
+                .collect::<Result<Vec<u16>, DecodeError>>()?;
+
+            if !values.is_sorted_by(|left, right| left < right) {
+                return Err(DecodeError::Invalid("values are not sorted"));
+            }
+
+            Container::Array(values)
+        } else {
+            let mut words = Box::new([0; BITSET_WORDS]);
+            for word in words.iter_mut() {
+                *word = reader.read_u64()?;
+            }
+
+            Container::Bitset(Bitset::from_words(words))
+        };
+
+        if container.len() != len {
+            return Err(DecodeError::Invalid(
+                "number of values doesn't match the header",
+            ));
+        }
+
+        Ok(container)
+    }
+
+    pub(crate) fn to_bitset(&self) -> Bitset {
+        match self {
+            Container::Bitset(bitset) => bitset.clone(),
+            _ => {
+                let mut bitset = Bitset::new();
+                for value in self.iter() {
+                    bitset.insert(value);
+                }
+                bitset
+            }
+        }
+    }
+
+    pub(crate) fn union(&self, other: &Container) -> Container {
+        match (self, other) {
+            (Container::Array(left), Container::Array(right)) => {
+                Container::from_sorted_values(merge_sorted(left, right, true, true, true))
+            }
+            _ => combine_bitsets(self, other, |left, right| left | right),
+        }
+    }
+
+    pub(crate) fn intersection(&self, other: &Container) -> Container {
+        match (self, other) {
+            // an array is never larger than the intersection, so its values are probed against the other container
+            (Container::Array(values), container) | (container, Container::Array(values)) => {
+                Container::Array(
+                    values
+                        .iter()
+                        .copied()
+                        .filter(|value| container.contains(*value))
+                        .collect(),
+                )
+            }
+            _ => combine_bitsets(self, other, |left, right| left & right),
+        }
+    }
+
+    pub(crate) fn symmetric_difference(&self, other: &Container) -> Container {
+        match (self, other) {
+            (Container::Array(left), Container::Array(right)) => {
+                Container::from_sorted_values(merge_sorted(left, right, true, false, true))
+            }
+            _ => combine_bitsets(self, other, |left, right| left ^ right),
+        }
+    }
+
+    pub(crate) fn difference(&self, other: &Container) -> Container {
+        match self {
+            // the difference is never larger than the array, so its values are probed against the other container
+            Container::Array(values) => Container::Array(
+                values
+                    .iter()
+                    .copied()
+                    .filter(|value| !other.contains(*value))
+                    .collect(),
+            ),
+            _ => combine_bitsets(self, other, |left, right| left & !right),
+        }
+    }
+}
+
+impl PartialEq for Container {
+    fn eq(&self, other: &Self) -> bool {
+        // containers with the same values are equal, no matter which representation they use
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Container {}
+
+impl Debug for Container {
+    /// Shows the representation and the number of values, but not the values themselves, as a bitset alone has 1024
+    /// u64s.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Container::Array(values) => {
+                f.debug_struct("Array").field("len", &values.len()).finish()
+            }
+            Container::Bitset(bitset) => {
+                f.debug_struct("Bitset").field("len", &bitset.len).finish()
+            }
+            Container::Run(runs) => f
+                .debug_struct("Run")
+                .field("len", &self.len())
+                .field("runs", &runs.len())
+                .finish(),
+        }
+    }
+}
+
+/// Combines the bitsets of both containers word by word.
+fn combine_bitsets(left: &Container, right: &Container, op: impl Fn(u64, u64) -> u64) -> Container {
+    let mut words = left.to_bitset().words;
+    let right = right.to_bitset();
+
+    for (left, right) in iter::zip(words.iter_mut(), right.words.iter()) {
+        *left = op(*left, *right);
+    }
+
+    Container::from_bitset(Bitset::from_words(words))
+}
+
+/// Merges two sorted slices, keeping values only present on the left, in both, or only present on the right.
+fn merge_sorted(
+    left: &[u16],
+    right: &[u16],
+    keep_left: bool,
+    keep_both: bool,
+    keep_right: bool,
+) -> Vec<u16> {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.iter().peekable();
+    let mut right = right.iter().peekable();
+
+    loop {
+        match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) if l < r => {
+                if keep_left {
+                    merged.push(**l);
+                }
+                left.next();
+            }
+            (Some(l), Some(r)) if l > r => {
+                if keep_right {
+                    merged.push(**r);
+                }
+                right.next();
+            }
+            (Some(l), Some(_)) => {
+                if keep_both {
+                    merged.push(**l);
+                }
+                left.next();
+                right.next();
+            }
+            (Some(_), None) => {
+                if keep_left {
+                    merged.extend(left);
+                }
+                break;
+            }
+            (None, Some(_)) => {
+                if keep_right {
+                    merged.extend(right);
+                }
+                break;
+            }
+            (None, None) => break,
+        }
+    }
+
+    merged
+}
+
+/// Groups sorted values into `(start, length - 1)` runs.
+fn collect_runs(values: impl Iterator<Item = u16>) -> Vec<(u16, u16)> {
+    let mut runs: Vec<(u16, u16)> = Vec::new();
+
+    for value in values {
+        match runs.last_mut() {
+            Some((start, length))
+                if u32::from(*start) + u32::from(*length) + 1 == u32::from(value) =>
+            {
+                *length += 1
+            }
+            _ => runs.push((value, 0)),
+        }
+    }
+
+    runs
+}
+
+fn iter_bitset(words: &[u64; BITSET_WORDS]) -> BitsetIter<'_> {
+    BitsetIter {
+        words: words.iter().enumerate(),
+        word: 0,
+        word_base: 0,
+    }
+}
+
+fn iter_runs(runs: &[(u16, u16)]) -> RunIter<'_> {
+    RunIter {
+        runs: runs.iter(),
+        current: None,
+    }
+}
+
+/// Iterator over the values of a container, in ascending order.
+#[derive(Clone)]
+pub(crate) enum ContainerIter<'a> {
+    Array(slice::Iter<'a, u16>),
+    Bitset(BitsetIter<'a>),
+    Run(RunIter<'a>),
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ContainerIter::Array(values) => values.next().copied(),
+            ContainerIter::Bitset(bitset) => bitset.next(),
+            ContainerIter::Run(runs) => runs.next(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct BitsetIter<'a> {
+    words: iter::Enumerate<slice::Iter<'a, u64>>,
+    word: u64,
+    word_base: usize,
+}
+
+impl Iterator for BitsetIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip u64s without any bit set instead of probing every single value
+        while self.word == 0 {
+            let (word_index, word) = self.words.next()?;
+            self.word = *word;
+            self.word_base = word_index * 64;
+        }
+
+        let bit_index_in_word = self.word.trailing_zeros() as usize;
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.word &= self.word - 1;
+
+        Some((self.word_base + bit_index_in_word) as u16)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct RunIter<'a> {
+    runs: slice::Iter<'a, (u16, u16)>,
+    current: Option<RangeInclusive<u16>>,
+}
+
+impl Iterator for RunIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(value);
+            }
+
+            let (start, length) = self.runs.next()?;
+            self.current = Some(*start..=start + length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_switches_between_array_and_bitset() {
+        // given
+        let mut container = Container::new();
+
+        // when
+        for value in 0..=ARRAY_MAX_LEN as u16 {
+            container.insert(value * 2);
+        }
+
+        // then
+        assert!(matches!(container, Container::Bitset(_)));
+        assert_eq!(container.len(), ARRAY_MAX_LEN as u32 + 1);
+
+        container.remove(0);
+        assert!(matches!(container, Container::Array(_)));
+        assert_eq!(container.len(), ARRAY_MAX_LEN as u32);
+        assert!(container.contains(2));
+        assert!(!container.contains(3));
+    }
+
+    #[test]
+    fn it_stores_ranges_as_runs() {
+        // given
+        let mut container = Container::new();
+
+        // when
+        container.insert_range(10..=20_000);
+        container.insert_range(30_000..=u16::MAX);
+
+        // then
+        assert!(matches!(container, Container::Run(_)));
+        assert_eq!(container.len(), 19_991 + 35_536);
+        assert!(container.contains(10));
+        assert!(container.contains(20_000));
+        assert!(!container.contains(20_001));
+        assert!(container.contains(u16::MAX));
+        assert_eq!(container.min(), Some(10));
+        assert_eq!(container.max(), Some(u16::MAX));
+
+        container.remove(15);
+        assert!(matches!(container, Container::Run(_)));
+        assert!(!container.contains(15));
+        assert_eq!(container.number_of_runs(), 3);
+    }
+
+    #[test]
+    fn it_modifies_runs_in_place() {
+        // given
+        let mut container = Container::Run(vec![(10, 9), (30, 0), (u16::MAX, 0)]);
+
+        // when
+        container.insert(20);
+        container.insert(29);
+        container.insert(9);
+        container.insert(0);
+        container.remove(u16::MAX);
+        container.remove(15);
+        container.remove(9);
+        container.remove(30);
+        let split = container.clone();
+        container.insert(15);
+
+        // then
+        assert!(matches!(container, Container::Run(_)));
+        assert!(!container.insert(10));
+        assert!(!container.remove(9));
+        assert_eq!(split.number_of_runs(), 4);
+        assert!(!split.contains(15));
+        assert_eq!(
+            container.iter().collect::<Vec<u16>>(),
+            [0].into_iter()
+                .chain(10..=20)
+                .chain([29])
+                .collect::<Vec<u16>>()
+        );
+        assert_eq!(container.number_of_runs(), 3);
+    }
+
+    #[test]
+    fn it_gives_up_runs_once_they_are_larger_than_an_array() {
+        // given
+        let mut container = Container::new();
+        container.insert_range(0..=999);
+        assert!(matches!(container, Container::Run(_)));
+
+        // when
+        for value in (1..1_000).step_by(2) {
+            container.remove(value);
+        }
+
+        // then
+        assert!(matches!(container, Container::Array(_)));
+        assert_eq!(container.len(), 500);
+        assert_eq!(container.number_of_runs(), 500);
+    }
+
+    #[test]
+    fn it_inserts_ranges_into_bitsets_by_u64() {
+        // given
+        let mut bitset = Bitset::new();
+        bitset.insert(5);
+
+        // when
+        bitset.insert_range(3..=130);
+        bitset.insert_range(u16::MAX..=u16::MAX);
+
+        // then
+        assert_eq!(bitset.len, 129);
+        assert_eq!(
+            iter_bitset(&bitset.words).collect::<Vec<u16>>(),
+            (3..=130).chain([u16::MAX]).collect::<Vec<u16>>()
+        );
+    }
+
+    #[test]
+    fn it_combines_containers_of_different_representations() {
+        // given
+        let array = Container::Array(vec![1, 5, 9, 40_000]);
+        let mut run = Container::new();
+        run.insert_range(0..=9);
+
+        // then
+        assert_eq!(
+            array.union(&run).iter().collect::<Vec<u16>>(),
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 40_000]
+        );
+        assert_eq!(
+            array.intersection(&run).iter().collect::<Vec<u16>>(),
+            [1, 5, 9]
+        );
+        assert_eq!(
+            run.intersection(&array).iter().collect::<Vec<u16>>(),
+            [1, 5, 9]
+        );
+        assert_eq!(
+            array
+                .symmetric_difference(&run)
+                .iter()
+                .collect::<Vec<u16>>(),
+            [0, 2, 3, 4, 6, 7, 8, 40_000]
+        );
+        assert_eq!(
+            array.difference(&run).iter().collect::<Vec<u16>>(),
+            [40_000]
+        );
+        assert_eq!(
+            run.difference(&array).iter().collect::<Vec<u16>>(),
+            [0, 2, 3, 4, 6, 7, 8]
+        );
+    }
+}
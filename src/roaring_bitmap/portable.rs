@@ -0,0 +1,188 @@
+//! Portable serialization format shared by the Roaring implementations for Java, Go, C and others, see
+//! <https://github.com/RoaringBitmap/RoaringFormatSpec>.
+
+use super::RoaringBitmap;
+use super::container::Container;
+use crate::error::{ByteReader, DecodeError};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Cookie of bitmaps with at least one run container, followed by the number of containers minus one in the upper
+/// 16 bits.
+const SERIAL_COOKIE: u16 = 12347;
+
+/// Cookie of bitmaps without any run container, followed by the number of containers as a separate u32.
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+
+/// Bitmaps with run containers only store the offsets of their containers if they have at least this many.
+const NO_OFFSET_THRESHOLD: usize = 4;
+
+impl RoaringBitmap {
+    /// Serializes the bitmap into the portable Roaring format, which can be read by other Roaring implementations.
+    pub fn to_portable_bytes(&self) -> Vec<u8> {
+        let number_of_containers = self.containers.len();
+        let has_runs = self
+            .containers
+            .iter()
+            .any(|(_, container)| matches!(container, Container::Run(_)));
+
+        let mut bytes = Vec::new();
+
+        if has_runs {
+            let cookie = u32::from(SERIAL_COOKIE) | ((number_of_containers as u32 - 1) << 16);
+            bytes.extend(cookie.to_le_bytes());
+
+            // one bit per container, telling whether it is a run container
+            let mut run_flags = vec![0u8; number_of_containers.div_ceil(8)];
+            for (position, (_, container)) in self.containers.iter().enumerate() {
+                if matches!(container, Container::Run(_)) {
+                    run_flags[position / 8] |= 0b1 << (position % 8);
+                }
+            }
+            bytes.extend(run_flags);
+        } else {
+            bytes.extend(SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+            bytes.extend((number_of_containers as u32).to_le_bytes());
+        }
+
+        for (key, container) in &self.containers {
+            bytes.extend(key.to_le_bytes());
+            bytes.extend(((container.len() - 1) as u16).to_le_bytes());
+        }
+
+        if !has_runs || number_of_containers >= NO_OFFSET_THRESHOLD {
+            let mut offset = bytes.len() + 4 * number_of_containers;
+            for (_, container) in &self.containers {
+                bytes.extend((offset as u32).to_le_bytes());
+                offset += container.portable_size();
+            }
+        }
+
+        for (_, container) in &self.containers {
+            container.write_portable(&mut bytes);
+        }
+
+        bytes
+    }
+
+    /// Deserializes a bitmap from the portable Roaring format, as written by other Roaring implementations.
+    pub fn from_portable_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = ByteReader::new(bytes);
+
+        let cookie = reader.read_u32()?;
+        let (number_of_containers, run_flags) = if cookie as u16 == SERIAL_COOKIE {
+            let number_of_containers = (cookie >> 16) as usize + 1;
+            let run_flags = reader.take(number_of_containers.div_ceil(8))?;
+
+            (number_of_containers, Some(run_flags))
+        } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            (reader.read_u32()? as usize, None)
+        } else {
+            return Err(DecodeError::UnknownFormat);
+        };
+
+        let mut headers = Vec::new();
+        for _ in 0..number_of_containers {
+            let key = reader.read_u16()?;
+            let len = u32::from(reader.read_u16()?) + 1;
+
+            // keys must be strictly ascending, like the containers of a bitmap
+            if headers.last().is_some_and(|(last_key, _)| *last_key >= key) {
+                return Err(DecodeError::Invalid("container keys are not sorted"));
+            }
+
+            headers.push((key, len));
+        }
+
+        // the containers are read sequentially, so their offsets aren't needed
+        if run_flags.is_none() || number_of_containers >= NO_OFFSET_THRESHOLD {
+            reader.take(4 * number_of_containers)?;
+        }
+
+        let mut containers = Vec::with_capacity(number_of_containers);
+        for (position, (key, len)) in headers.into_iter().enumerate() {
+            let is_run =
+                run_flags.is_some_and(|flags| (flags[position / 8] >> (position % 8)) & 0b1 == 1);
+
+            containers.push((key, Container::read_portable(&mut reader, len, is_run)?));
+        }
+
+        reader.finish()?;
+
+        Ok(RoaringBitmap { containers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_writes_the_layout_of_the_specification() {
+        // given
+        let bm: RoaringBitmap = [1, 2, 3].into_iter().collect();
+
+        // when
+        let bytes = bm.to_portable_bytes();
+
+        // then
+        assert_eq!(
+            bytes,
+            vec![
+                0x3a, 0x30, 0, 0, // cookie without run containers
+                1, 0, 0, 0, // number of containers
+                0, 0, 2, 0, // key and number of values minus one
+                16, 0, 0, 0, // offset of the container
+                1, 0, 2, 0, 3, 0, // values
+            ]
+        );
+    }
+
+    #[test]
+    fn it_round_trips_all_container_kinds() {
+        for number_of_run_containers in [0, 1, 5] {
+            // given
+            let mut bm: RoaringBitmap = [7, 70_000, u32::MAX].into_iter().collect();
+            bm.extend((1 << 20..(1 << 20) + 60_000).step_by(2));
+            for key in 0..number_of_run_containers {
+                bm.set_range((100 + key) << 16..((100 + key) << 16) + 1_000);
+            }
+
+            // when
+            let decoded = RoaringBitmap::from_portable_bytes(&bm.to_portable_bytes());
+
+            // then
+            assert_eq!(decoded, Ok(bm));
+        }
+    }
+
+    #[test]
+    fn it_rejects_malformed_input() {
+        // given
+        let bytes = [1, 2, 3]
+            .into_iter()
+            .collect::<RoaringBitmap>()
+            .to_portable_bytes();
+
+        // then
+        assert_eq!(
+            RoaringBitmap::from_portable_bytes(&[0, 0, 0, 0]),
+            Err(DecodeError::UnknownFormat)
+        );
+        assert_eq!(
+            RoaringBitmap::from_portable_bytes(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+        assert!(matches!(
+            RoaringBitmap::from_portable_bytes(&[bytes.as_slice(), &[0]].concat()),
+            Err(DecodeError::Invalid(_))
+        ));
+
+        let mut unsorted = bytes.clone();
+        unsorted[16..18].copy_from_slice(&5u16.to_le_bytes());
+        assert!(matches!(
+            RoaringBitmap::from_portable_bytes(&unsorted),
+            Err(DecodeError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions between [SimpleBitmap] and the bitmaps of the [roaring] crate.
+
+use crate::SimpleBitmap;
+
+impl From<&roaring::RoaringBitmap> for SimpleBitmap {
+    fn from(bitmap: &roaring::RoaringBitmap) -> Self {
+        // the values are iterated in ascending order, which lets collect write every u32 only once
+        bitmap.iter().collect()
+    }
+}
+
+impl From<roaring::RoaringBitmap> for SimpleBitmap {
+    fn from(bitmap: roaring::RoaringBitmap) -> Self {
+        SimpleBitmap::from(&bitmap)
+    }
+}
+
+impl From<&SimpleBitmap> for roaring::RoaringBitmap {
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        roaring::RoaringBitmap::from_sorted_iter(bitmap.iter())
+            .expect("values of a bitmap are iterated in ascending order")
+    }
+}
+
+impl From<SimpleBitmap> for roaring::RoaringBitmap {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        roaring::RoaringBitmap::from(&bitmap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RoaringBitmap;
+
+    #[test]
+    fn it_converts_from_and_to_roaring_bitmaps() {
+        // given
+        let bm: SimpleBitmap = (0..100_000).filter(|index| index % 3 == 0).collect();
+
+        // when
+        let roaring = roaring::RoaringBitmap::from(&bm);
+
+        // then
+        assert!(roaring.iter().eq(&bm));
+        assert_eq!(SimpleBitmap::from(roaring), bm);
+    }
+
+    #[test]
+    fn it_exchanges_the_portable_format_with_the_roaring_crate() {
+        // given
+        let mut ours: RoaringBitmap = [7, 70_000, u32::MAX].into_iter().collect();
+        ours.extend((1 << 20..(1 << 20) + 60_000).step_by(2));
+        for key in 0..5 {
+            ours.set_range((100 + key) << 16..((100 + key) << 16) + 1_000);
+        }
+
+        let mut theirs = roaring::RoaringBitmap::new();
+        theirs.extend(&ours);
+
+        // when
+        let read_by_them =
+            roaring::RoaringBitmap::deserialize_from(ours.to_portable_bytes().as_slice()).unwrap();
+
+        let mut their_bytes = Vec::new();
+        theirs.serialize_into(&mut their_bytes).unwrap();
+        let read_by_us = RoaringBitmap::from_portable_bytes(&their_bytes).unwrap();
+
+        // then
+        assert_eq!(read_by_them, theirs);
+        assert_eq!(read_by_us, ours);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{Bitmap, Bitmap64, RoaringBitmap, RoaringOnes};
+use alloc::collections::BTreeMap;
+use alloc::collections::btree_map;
+use core::mem;
+use core::ops::{BitAnd, BitOr};
+
+/// Implementation of a [Bitmap64] for 64-bit indices, e.g. snowflake IDs.
+///
+/// Values are partitioned by their upper 32 bits into segments, which each store the lower 32 bits in a
+/// [RoaringBitmap]. Only segments with present values are stored, so memory grows with the number of values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SegmentedBitmap {
+    // segments are never empty
+    segments: BTreeMap<u32, RoaringBitmap>,
+}
+
+impl SegmentedBitmap {
+    pub fn new() -> Self {
+        Self {
+            segments: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        // empty segments are removed eagerly
+        self.segments.is_empty()
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> SegmentedOnes<'_> {
+        SegmentedOnes {
+            segments: self.segments.iter(),
+            current: None,
+        }
+    }
+}
+
+/// Splits an index into the key of its segment and the value within the segment.
+fn split(index: u64) -> (u32, u32) {
+    ((index >> 32) as u32, index as u32)
+}
+
+fn join(key: u32, low: u32) -> u64 {
+    (u64::from(key) << 32) | u64::from(low)
+}
+
+impl Bitmap64 for SegmentedBitmap {
+    fn set(&mut self, index: u64) {
+        let (key, low) = split(index);
+
+        self.segments
+            .entry(key)
+            .or_insert_with(RoaringBitmap::new)
+            .set(low);
+    }
+
+    fn clear(&mut self, index: u64) {
+        let (key, low) = split(index);
+
+        if let btree_map::Entry::Occupied(mut entry) = self.segments.entry(key) {
+            entry.get_mut().clear(low);
+
+            // segments are never kept empty
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+    }
+
+    fn get(&self, index: u64) -> bool {
+        let (key, low) = split(index);
+
+        self.segments
+            .get(&key)
+            .is_some_and(|segment| segment.get(low))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.segments.values().map(Bitmap::count_ones).sum()
+    }
+}
+
+impl BitOr for SegmentedBitmap {
+    type Output = SegmentedBitmap;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        for (key, segment) in rhs.segments {
+            let union = self.segments.entry(key).or_insert_with(RoaringBitmap::new);
+            *union = mem::replace(union, RoaringBitmap::new()) | segment;
+        }
+
+        self
+    }
+}
+
+impl BitAnd for SegmentedBitmap {
+    type Output = SegmentedBitmap;
+
+    fn bitand(self, mut rhs: Self) -> Self::Output {
+        // only segments present in both bitmaps can share values
+        let segments = self
+            .segments
+            .into_iter()
+            .filter_map(|(key, segment)| {
+                let intersection = segment & rhs.segments.remove(&key)?;
+                (!intersection.is_empty()).then_some((key, intersection))
+            })
+            .collect();
+
+        SegmentedBitmap { segments }
+    }
+}
+
+impl FromIterator<u64> for SegmentedBitmap {
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        let mut bitmap = SegmentedBitmap::new();
+        for index in iter {
+            bitmap.set(index);
+        }
+
+        bitmap
+    }
+}
+
+/// Iterator over the indices of the values present in a [SegmentedBitmap], in ascending order.
+///
+/// Created by [SegmentedBitmap::iter].
+#[derive(Clone)]
+pub struct SegmentedOnes<'a> {
+    segments: btree_map::Iter<'a, u32, RoaringBitmap>,
+    current: Option<(u32, RoaringOnes<'a>)>,
+}
+
+impl Iterator for SegmentedOnes<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.current
+                && let Some(low) = values.next()
+            {
+                return Some(join(*key, low));
+            }
+
+            let (key, segment) = self.segments.next()?;
+            self.current = Some((*key, segment.iter()));
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a SegmentedBitmap {
+    type Item = u64;
+    type IntoIter = SegmentedOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_and_gets_64_bit_indices() {
+        // given
+        let mut bm = SegmentedBitmap::new();
+
+        // when
+        bm.set(1_541_815_603_606_036_480);
+        bm.set(1_541_815_603_606_036_481);
+        bm.set(7);
+        bm.set(u64::MAX);
+        bm.clear(7);
+
+        // then
+        assert!(bm.get(1_541_815_603_606_036_480));
+        assert!(!bm.get(7));
+        assert_eq!(bm.count_ones(), 3);
+        assert_eq!(
+            bm.iter().collect::<Vec<u64>>(),
+            vec![
+                1_541_815_603_606_036_480,
+                1_541_815_603_606_036_481,
+                u64::MAX
+            ]
+        );
+    }
+
+    #[test]
+    fn it_combines_segments_of_both_bitmaps() {
+        // given
+        let left: SegmentedBitmap = [1, 1 << 40, (1 << 40) + 1].into_iter().collect();
+        let right: SegmentedBitmap = [2, (1 << 40) + 1, 1 << 50].into_iter().collect();
+
+        // then
+        assert_eq!(
+            (left.clone() | right.clone()).iter().collect::<Vec<u64>>(),
+            vec![1, 2, 1 << 40, (1 << 40) + 1, 1 << 50]
+        );
+        assert_eq!(
+            (left & right).iter().collect::<Vec<u64>>(),
+            vec![(1 << 40) + 1]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Bitmap, SimpleBitmap};
+use core::ptr;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Growable bitmap that can be modified concurrently from many threads, as all methods take `&self`.
+///
+/// The u32s are distributed round-robin over a fixed number of shards, which are each guarded by their own lock. Threads
+/// touching different u32s therefore rarely contend for the same lock, even if their indices are close to each other.
+pub struct ShardedBitmap {
+    shards: Vec<RwLock<SimpleBitmap>>,
+}
+
+impl ShardedBitmap {
+    /// Creates an empty bitmap distributed over the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of shards is zero.
+    pub fn new(shards: usize) -> Self {
+        assert!(shards > 0, "a sharded bitmap needs at least one shard");
+
+        Self {
+            shards: (0..shards)
+                .map(|_| RwLock::new(SimpleBitmap::new()))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of shards.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn set(&self, index: u32) {
+        let (shard, index_in_shard) = self.locate(index);
+
+        write(&self.shards[shard]).set(index_in_shard);
+    }
+
+    pub fn clear(&self, index: u32) {
+        let (shard, index_in_shard) = self.locate(index);
+
+        write(&self.shards[shard]).clear(index_in_shard);
+    }
+
+    pub fn get(&self, index: u32) -> bool {
+        let (shard, index_in_shard) = self.locate(index);
+
+        read(&self.shards[shard]).get(index_in_shard)
+    }
+
+    /// Returns the number of present values.
+    ///
+    /// The shards are locked one after another, so concurrent modifications may or may not be reflected.
+    pub fn count_ones(&self) -> u64 {
+        self.shards
+            .iter()
+            .map(|shard| read(shard).count_ones())
+            .sum()
+    }
+
+    /// Sets all values present in `other`.
+    ///
+    /// Shards are combined pairwise if both bitmaps use the same number of shards, otherwise the values of `other` are
+    /// set one by one. At most one lock is held at a time, so concurrent unions in opposite directions cannot deadlock.
+    pub fn union_with(&self, other: &ShardedBitmap) {
+        // the union with itself changes nothing
+        if ptr::eq(self, other) {
+            return;
+        }
+
+        if self.shards() == other.shards() {
+            for (left, right) in self.shards.iter().zip(&other.shards) {
+                // holding the read lock of `right` while waiting for the write lock of `left` could deadlock with a
+                // union in the opposite direction
+                let right = read(right).clone();
+                *write(left) |= &right;
+            }
+        } else {
+            for index in &SimpleBitmap::from(other) {
+                self.set(index);
+            }
+        }
+    }
+
+    /// Returns the shard holding the given index, together with the index within that shard.
+    fn locate(&self, index: u32) -> (usize, u32) {
+        let u32_index = (index / 32) as usize;
+        let u32_index_in_shard = (u32_index / self.shards.len()) as u32;
+
+        (
+            u32_index % self.shards.len(),
+            u32_index_in_shard * 32 + (index & 0b11111),
+        )
+    }
+}
+
+// the bitmap of a shard is consistent after every single operation, so a panic of another thread holding the lock does
+// not leave it in a broken state
+fn read(shard: &RwLock<SimpleBitmap>) -> RwLockReadGuard<'_, SimpleBitmap> {
+    shard.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write(shard: &RwLock<SimpleBitmap>) -> RwLockWriteGuard<'_, SimpleBitmap> {
+    shard.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl From<&ShardedBitmap> for SimpleBitmap {
+    /// Takes a snapshot of the bitmap. Like [ShardedBitmap::count_ones], it may or may not reflect concurrent
+    /// modifications.
+    fn from(bitmap: &ShardedBitmap) -> Self {
+        let mut words = Vec::new();
+
+        for (shard_index, shard) in bitmap.shards.iter().enumerate() {
+            for (u32_index_in_shard, bucket) in read(shard).words().iter().enumerate() {
+                let u32_index = u32_index_in_shard * bitmap.shards.len() + shard_index;
+
+                // if there is too little u32s in the words vec, it has to be extended
+                if u32_index >= words.len() {
+                    words.resize(u32_index + 1, 0);
+                }
+
+                words[u32_index] = *bucket;
+            }
+        }
+
+        SimpleBitmap::from_words(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn it_sets_and_gets_bits_across_shards() {
+        // given
+        let bm = ShardedBitmap::new(3);
+
+        // when
+        for index in [0, 31, 32, 64, 96, 1_000, 1_000_000] {
+            bm.set(index);
+        }
+        bm.clear(1_000);
+
+        // then
+        assert!(bm.get(31));
+        assert!(bm.get(96));
+        assert!(bm.get(1_000_000));
+        assert!(!bm.get(1_000));
+        assert_eq!(bm.count_ones(), 6);
+    }
+
+    #[test]
+    fn it_sets_bits_from_many_threads() {
+        // given
+        let bm = ShardedBitmap::new(4);
+
+        // when
+        thread::scope(|scope| {
+            for thread_index in 0..4 {
+                let bm = &bm;
+                scope.spawn(move || {
+                    for index in (thread_index..100_000).step_by(4) {
+                        bm.set(index);
+                    }
+                });
+            }
+        });
+
+        // then
+        assert_eq!(bm.count_ones(), 100_000);
+        assert_eq!(SimpleBitmap::from(&bm), (0..100_000).collect());
+    }
+
+    #[test]
+    fn it_builds_unions_of_differently_sharded_bitmaps() {
+        // given
+        let left = ShardedBitmap::new(2);
+        let same = ShardedBitmap::new(2);
+        let different = ShardedBitmap::new(5);
+        left.set(1);
+        same.set(100);
+        different.set(10_000);
+
+        // when
+        left.union_with(&same);
+        left.union_with(&different);
+        left.union_with(&left);
+
+        // then
+        assert_eq!(
+            SimpleBitmap::from(&left).iter().collect::<Vec<u32>>(),
+            vec![1, 100, 10_000]
+        );
+    }
+
+    #[test]
+    fn it_builds_unions_in_opposite_directions_concurrently() {
+        // given
+        let a = ShardedBitmap::new(4);
+        let b = ShardedBitmap::new(4);
+        for index in 0..1_000 {
+            a.set(index * 2);
+            b.set(index * 2 + 1);
+        }
+
+        // when
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..1_000 {
+                    a.union_with(&b);
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..1_000 {
+                    b.union_with(&a);
+                }
+            });
+        });
+
+        // then
+        assert_eq!(SimpleBitmap::from(&a), (0..2_000).collect());
+        assert_eq!(SimpleBitmap::from(&b), (0..2_000).collect());
+    }
+}
@@ -0,0 +1,43 @@
+//! Similarity measures between bitmaps, built on the count-only set operations.
+
+use crate::SimpleBitmap;
+
+/// Computes the Jaccard similarity `|a ∩ b| / |a ∪ b|` of two bitmaps.
+///
+/// Two empty bitmaps are identical and therefore have a similarity of `1.0`.
+pub fn jaccard(a: &SimpleBitmap, b: &SimpleBitmap) -> f64 {
+    let union_len = a.union_len(b);
+
+    if union_len == 0 {
+        return 1.0;
+    }
+
+    a.intersection_len(b) as f64 / union_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+
+    #[test]
+    fn it_computes_the_jaccard_similarity() {
+        // given
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(40);
+        peated_bm.set(70);
+        peated_bm.set(71);
+
+        // then
+        assert_eq!(jaccard(&speyside_bm, &peated_bm), 2.0 / 5.0);
+        assert_eq!(jaccard(&speyside_bm, &speyside_bm), 1.0);
+        assert_eq!(jaccard(&speyside_bm, &SimpleBitmap::new()), 0.0);
+        assert_eq!(jaccard(&SimpleBitmap::new(), &SimpleBitmap::new()), 1.0);
+    }
+}
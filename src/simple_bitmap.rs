@@ -1,125 +1,1972 @@
+pub use delta::BitmapDelta;
+pub use iterators::{CombinedOnes, Cursor, Ones, Runs, RunsInclusive, Zeros};
+pub use stats::{BitmapStats, Representation};
+pub use view::{BitmapView, ViewOnes};
+
 use crate::Bitmap;
-use std::fmt::{Debug, Formatter};
-use std::ops::BitOr;
-use std::{cmp, iter};
+use crate::error::DecodeError;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Range, Shl, ShlAssign, Shr, ShrAssign, Sub,
+};
+use core::{cmp, iter};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::hash::BuildHasher;
+
+mod binary;
+mod delta;
+mod iterators;
+mod kernels;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rand")]
+mod sampling;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod stats;
+mod text;
+mod view;
+
+/// Non-optimized implementation of a [Bitmap].
+#[derive(Clone)]
+pub struct SimpleBitmap {
+    bits: Vec<u32>,
+}
+
+impl SimpleBitmap {
+    pub fn new() -> Self {
+        Self { bits: Vec::new() }
+    }
+
+    /// Creates an empty bitmap that can hold values up to `max_index` without reallocating.
+    pub fn with_capacity(max_index: u32) -> Self {
+        Self {
+            bits: Vec::with_capacity((max_index / 32) as usize + 1),
+        }
+    }
+
+    /// Reserves capacity so that values up to `max_index` can be set without reallocating.
+    pub fn reserve(&mut self, max_index: u32) {
+        let required_len = (max_index / 32) as usize + 1;
+
+        self.bits
+            .reserve(required_len.saturating_sub(self.bits.len()));
+    }
+
+    /// Sets the presence of all values in the given range.
+    pub fn set_range(&mut self, range: Range<u32>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // if there is too little u32s in the bits vec, it has to be extended
+        let required_len = ((range.end - 1) / 32) as usize + 1;
+        if required_len > self.bits.len() {
+            self.bits.resize(required_len, 0);
+        }
+
+        for (u32_index_in_bits_vec, mask) in range_masks(range) {
+            self.bits[u32_index_in_bits_vec] |= mask;
+        }
+    }
+
+    /// Clears the presence of all values in the given range.
+    pub fn clear_range(&mut self, range: Range<u32>) {
+        // u32s beyond the end of the bits vec are absent anyway, so the vec never has to be extended here
+        for (u32_index_in_bits_vec, mask) in range_masks(range) {
+            match self.bits.get_mut(u32_index_in_bits_vec) {
+                Some(bucket) => *bucket &= !mask,
+                None => break,
+            }
+        }
+    }
+
+    /// Flips the presence of all values in the given range.
+    pub fn flip_range(&mut self, range: Range<u32>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // if there is too little u32s in the bits vec, it has to be extended
+        let required_len = ((range.end - 1) / 32) as usize + 1;
+        if required_len > self.bits.len() {
+            self.bits.resize(required_len, 0);
+        }
+
+        for (u32_index_in_bits_vec, mask) in range_masks(range) {
+            self.bits[u32_index_in_bits_vec] ^= mask;
+        }
+    }
+
+    /// Returns a bitmap in which exactly the values below `universe` are present that are absent in this bitmap.
+    pub fn complement(&self, universe: u32) -> SimpleBitmap {
+        let len = universe.div_ceil(32) as usize;
+
+        let mut bits: Vec<u32> = (0..len)
+            .map(|u32_index_in_bits_vec| !self.bits.get(u32_index_in_bits_vec).unwrap_or(&0))
+            .collect();
+
+        // bits of the last u32 at or beyond the universe have to stay cleared
+        if !universe.is_multiple_of(32)
+            && let Some(last) = bits.last_mut()
+        {
+            *last &= !(u32::MAX << (universe & 0b11111));
+        }
+
+        SimpleBitmap { bits }
+    }
+
+    /// Splits the bitmap into the values below `index` and the values at or above it. The values of the second bitmap
+    /// are moved down by `index`, so that [SimpleBitmap::append_at] with the same index joins both again.
+    pub fn split_at(&self, index: u32) -> (SimpleBitmap, SimpleBitmap) {
+        let len = (index.div_ceil(32) as usize).min(self.bits.len());
+        let mut lower = self.bits[..len].to_vec();
+
+        // bits of the u32 holding the index are cleared from the index on
+        if !index.is_multiple_of(32)
+            && let Some(last) = lower.get_mut((index / 32) as usize)
+        {
+            *last &= !(u32::MAX << (index & 0b11111));
+        }
+
+        (SimpleBitmap { bits: lower }, self.clone() >> index)
+    }
+
+    /// Adds all values of `other` moved up by `offset` to this bitmap. Values that would exceed u32::MAX are dropped.
+    pub fn append_at(&mut self, offset: u32, other: &SimpleBitmap) {
+        or_shifted_into(&mut self.bits, other.trimmed_bits(), offset);
+    }
+
+    /// Returns `true` if all values in the given range are present. An empty range is always contained.
+    pub fn contains_range(&self, range: Range<u32>) -> bool {
+        // the u32s in the middle of the range are compared as a whole against the full mask
+        range_masks(range).all(|(u32_index_in_bits_vec, mask)| {
+            self.bits
+                .get(u32_index_in_bits_vec)
+                .is_some_and(|bucket| bucket & mask == mask)
+        })
+    }
+
+    /// Counts the values present within the given range.
+    pub fn count_ones_in_range(&self, range: Range<u32>) -> u64 {
+        count_ones_in(&self.bits, range)
+    }
+
+    /// Drops trailing zero u32s and releases the excess capacity of the bits vec.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self.trimmed_bits().len();
+
+        self.bits.truncate(len);
+        self.bits.shrink_to_fit();
+    }
+
+    /// Creates a bitmap from its u32s, where bit `i` of u32 `j` holds the value `j * 32 + i`.
+    pub(crate) fn from_words(bits: Vec<u32>) -> Self {
+        Self { bits }
+    }
+
+    /// Creates a bitmap from the bytes of its u32s in little-endian byte order. A partial u32 at the end is padded with
+    /// zero bytes.
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        let bits = bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut le_bytes = [0; 4];
+                le_bytes[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(le_bytes)
+            })
+            .collect();
+
+        Self { bits }
+    }
+
+    /// Returns the bytes of the u32s in little-endian byte order, without trailing zero bytes.
+    fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .trimmed_bits()
+            .iter()
+            .flat_map(|bucket| bucket.to_le_bytes())
+            .collect();
+
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+
+        bytes
+    }
+
+    /// Returns the u32s backing this bitmap.
+    pub(crate) fn words(&self) -> &[u32] {
+        &self.bits
+    }
+
+    /// Returns the bits vec without trailing zero u32s, which do not carry any information.
+    pub(crate) fn trimmed_bits(&self) -> &[u32] {
+        let len = self
+            .bits
+            .iter()
+            .rposition(|bucket| *bucket != 0)
+            .map_or(0, |last_non_zero| last_non_zero + 1);
+
+        &self.bits[..len]
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        // stop at the first u32 that has any bit set
+        self.bits.iter().all(|bucket| *bucket == 0)
+    }
+
+    /// Returns the lowest index at which a value is present.
+    pub fn first_set(&self) -> Option<u32> {
+        self.bits
+            .iter()
+            .enumerate()
+            .find(|(_, bucket)| **bucket != 0)
+            .map(|(u32_index_in_bits_vec, bucket)| {
+                u32_index_in_bits_vec as u32 * 32 + bucket.trailing_zeros()
+            })
+    }
+
+    /// Returns the highest index at which a value is present.
+    pub fn last_set(&self) -> Option<u32> {
+        // scan backwards, as trailing u32s might be zero after clearing bits
+        self.bits
+            .iter()
+            .enumerate()
+            .rfind(|(_, bucket)| **bucket != 0)
+            .map(|(u32_index_in_bits_vec, bucket)| {
+                u32_index_in_bits_vec as u32 * 32 + (31 - bucket.leading_zeros())
+            })
+    }
+
+    /// Returns the lowest index at or above `from` at which a value is present.
+    pub fn next_set_bit(&self, from: u32) -> Option<u32> {
+        iterators::next_index_where(&self.bits, u64::from(from), |bucket| bucket)
+            .map(|index| index as u32)
+    }
+
+    /// Returns the lowest index at or above `from` at which no value is present, e.g. the first free slot of an
+    /// allocator. Returns `None` only if all values from `from` up to u32::MAX are present.
+    pub fn next_clear_bit(&self, from: u32) -> Option<u32> {
+        // u32s beyond the end of the bits vec have all bits cleared
+        let index = iterators::next_index_where(&self.bits, u64::from(from), |bucket| !bucket)
+            .unwrap_or_else(|| cmp::max(u64::from(from), self.bits.len() as u64 * 32));
+
+        u32::try_from(index).ok()
+    }
+
+    /// Returns the highest index at or below `from` at which a value is present.
+    pub fn prev_set_bit(&self, from: u32) -> Option<u32> {
+        let last_u32_index = (from / 32) as usize;
+        let len = cmp::min(last_u32_index + 1, self.bits.len());
+
+        // scan backwards, bits above from are masked out in the u32 of from
+        self.bits[..len]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(u32_index_in_bits_vec, bucket)| {
+                let candidates = if u32_index_in_bits_vec == last_u32_index {
+                    bucket & (u32::MAX >> (31 - (from & 0b11111)))
+                } else {
+                    *bucket
+                };
+
+                (candidates != 0)
+                    .then(|| u32_index_in_bits_vec as u32 * 32 + (31 - candidates.leading_zeros()))
+            })
+    }
+
+    /// Clears the lowest present value and returns its index.
+    pub fn pop_first(&mut self) -> Option<u32> {
+        let index = self.first_set()?;
+        self.clear(index);
+
+        Some(index)
+    }
+
+    /// Clears the highest present value and returns its index.
+    pub fn pop_last(&mut self) -> Option<u32> {
+        let index = self.last_set()?;
+        self.clear(index);
+
+        Some(index)
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// Returns `true` if all values of this bitmap are also present in `other`.
+    pub fn is_subset(&self, other: &SimpleBitmap) -> bool {
+        // u32s beyond the end of other must not have any bit set
+        let other_buckets = other.bits.iter().chain(iter::repeat(&0));
+
+        iter::zip(&self.bits, other_buckets).all(|(left, right)| left & !right == 0)
+    }
+
+    /// Returns `true` if all values of `other` are also present in this bitmap.
+    pub fn is_superset(&self, other: &SimpleBitmap) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if no value is present in both this bitmap and `other`.
+    pub fn is_disjoint(&self, other: &SimpleBitmap) -> bool {
+        // stop at the first u32 that shares any bit
+        iter::zip(&self.bits, &other.bits).all(|(left, right)| left & right == 0)
+    }
+
+    /// Counts the values present in both this bitmap and `other`, without building the intersection.
+    pub fn intersection_len(&self, other: &SimpleBitmap) -> u64 {
+        iter::zip(&self.bits, &other.bits)
+            .map(|(left, right)| u64::from((left & right).count_ones()))
+            .sum()
+    }
+
+    /// Counts the values present in this bitmap or `other`, without building the union.
+    pub fn union_len(&self, other: &SimpleBitmap) -> u64 {
+        let (longer, shorter) = if self.bits.len() >= other.bits.len() {
+            (&self.bits, &other.bits)
+        } else {
+            (&other.bits, &self.bits)
+        };
+
+        // u32s of the shorter vec are padded with zeros to the length of the longer vec
+        let shorter_buckets = shorter.iter().chain(iter::repeat(&0));
+
+        iter::zip(longer, shorter_buckets)
+            .map(|(left, right)| u64::from((left | right).count_ones()))
+            .sum()
+    }
+
+    /// Counts the values present in this bitmap but not in `other`, without building the difference.
+    pub fn difference_len(&self, other: &SimpleBitmap) -> u64 {
+        // u32s of other are padded with zeros to the length of this bitmap's vec
+        let other_buckets = other.bits.iter().chain(iter::repeat(&0));
+
+        iter::zip(&self.bits, other_buckets)
+            .map(|(left, right)| u64::from((left & !right).count_ones()))
+            .sum()
+    }
+
+    /// Counts the values present strictly below the given index.
+    pub fn rank(&self, index: u32) -> u64 {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        // all u32s before the one holding the index are counted as a whole
+        let full_u32s: u64 = self
+            .bits
+            .iter()
+            .take(u32_index_in_bits_vec)
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum();
+
+        // only the bits below the index are counted in the u32 holding the index
+        let partial_u32 = self
+            .bits
+            .get(u32_index_in_bits_vec)
+            .map_or(0, |bucket| bucket & !(u32::MAX << bit_index_in_u32));
+
+        full_u32s + u64::from(partial_u32.count_ones())
+    }
+
+    /// Returns the index of the `k`-th present value, counting from zero.
+    pub fn select(&self, k: u64) -> Option<u32> {
+        let mut remaining = k;
+
+        for (u32_index_in_bits_vec, bucket) in self.bits.iter().enumerate() {
+            let ones = u64::from(bucket.count_ones());
+
+            // skip whole u32s as long as they hold fewer values than remaining
+            if remaining < ones {
+                return Some(
+                    u32_index_in_bits_vec as u32 * 32 + select_in_u32(*bucket, remaining as u32),
+                );
+            }
+
+            remaining -= ones;
+        }
+
+        None
+    }
+
+    /// Returns the present values in the range notation of the [Display] implementation, like `1, 6, 9-20, 42`.
+    pub fn to_range_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Reports how much memory the bitmap occupies and how much of it holds information.
+    pub fn memory_usage(&self) -> BitmapMemoryStats {
+        BitmapMemoryStats {
+            allocated_bytes: self.bits.capacity() * size_of::<u32>(),
+            words: self.bits.len(),
+            trailing_zero_words: self.bits.len() - self.trimmed_bits().len(),
+            density: self.density(),
+        }
+    }
+
+    /// Returns the share of set bits among all bits of the u32s, or 0 if there are no u32s.
+    fn density(&self) -> f64 {
+        if self.bits.is_empty() {
+            return 0.0;
+        }
+
+        self.count_ones() as f64 / (self.bits.len() as f64 * 32.0)
+    }
+
+    /// Returns the `n`-th present value, counting from zero, like [SimpleBitmap::select].
+    pub fn nth(&self, n: u64) -> Option<u32> {
+        self.select(n)
+    }
+
+    /// Returns the `k` highest present values in descending order, or all values if fewer are present.
+    pub fn top_k(&self, k: usize) -> Vec<u32> {
+        // the values are walked from the highest u32 down, so only the u32s holding the result are visited
+        self.iter().rev().take(k).collect()
+    }
+
+    /// Returns an iterator over the indices of the absent values below `upper_bound`, in ascending order.
+    pub fn iter_zeros(&self, upper_bound: u32) -> Zeros<'_> {
+        Zeros::new(&self.bits, upper_bound)
+    }
+
+    /// Sets the given indices, which don't have to be sorted or unique. Unlike [Extend::extend], the highest index is
+    /// looked up first, so the bits vec is extended at most once.
+    pub fn extend_from_slice(&mut self, indices: &[u32]) {
+        let Some(max_index) = indices.iter().max() else {
+            return;
+        };
+
+        let required_len = (max_index / 32) as usize + 1;
+        if required_len > self.bits.len() {
+            self.bits.resize(required_len, 0);
+        }
+
+        for index in indices {
+            self.bits[(index / 32) as usize] |= 0b1 << (index & 0b11111);
+        }
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive present values, in ascending order.
+    ///
+    /// A run that includes `u32::MAX` can't be represented by an exclusive range and ends at `u32::MAX`, use
+    /// [SimpleBitmap::runs_inclusive] to get it completely.
+    pub fn runs(&self) -> Runs<'_> {
+        Runs::new(&self.bits)
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive present values as inclusive ranges, in ascending
+    /// order.
+    pub fn runs_inclusive(&self) -> RunsInclusive<'_> {
+        RunsInclusive::new(&self.bits)
+    }
+
+    /// Returns an iterator over the indices of the values present in both this bitmap and `other`, in ascending order,
+    /// without building the intersection.
+    pub fn iter_and<'a>(&'a self, other: &'a SimpleBitmap) -> CombinedOnes<'a> {
+        let len = cmp::min(self.bits.len(), other.bits.len());
+        CombinedOnes::new(&self.bits, &other.bits, |left, right| left & right, len)
+    }
+
+    /// Returns an iterator over the indices of the values present in this bitmap or `other`, in ascending order,
+    /// without building the union.
+    pub fn iter_or<'a>(&'a self, other: &'a SimpleBitmap) -> CombinedOnes<'a> {
+        let len = cmp::max(self.bits.len(), other.bits.len());
+        CombinedOnes::new(&self.bits, &other.bits, |left, right| left | right, len)
+    }
+
+    /// Returns an iterator over the indices of the values present in this bitmap but not in `other`, in ascending
+    /// order, without building the difference.
+    pub fn iter_diff<'a>(&'a self, other: &'a SimpleBitmap) -> CombinedOnes<'a> {
+        CombinedOnes::new(
+            &self.bits,
+            &other.bits,
+            |left, right| left & !right,
+            self.bits.len(),
+        )
+    }
+
+    /// Returns a cursor over the indices of the present values, in ascending order, that can skip ahead via
+    /// [Cursor::advance_to].
+    pub fn cursor(&self) -> Cursor<'_> {
+        Cursor::new(&self.bits)
+    }
+
+    /// Flips the presence of a value at the given index and returns whether it is present afterwards.
+    pub fn toggle(&mut self, index: u32) -> bool {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        // if there is too little u32s in the bits vec, it has to be extended
+        if u32_index_in_bits_vec >= self.bits.len() {
+            self.bits.resize(u32_index_in_bits_vec + 1, 0);
+        }
+
+        let modified_u32 = self.bits[u32_index_in_bits_vec] ^ (0b1 << bit_index_in_u32);
+
+        self.bits[u32_index_in_bits_vec] = modified_u32;
+
+        ((modified_u32 >> bit_index_in_u32) & 0b1) == 1
+    }
+
+    /// Keeps only the values whose index satisfies the predicate, visiting the present values in ascending order.
+    pub fn retain(&mut self, mut f: impl FnMut(u32) -> bool) {
+        for (u32_index_in_bits_vec, bucket) in self.bits.iter_mut().enumerate() {
+            let mut remaining = *bucket;
+
+            // visit every set bit of the u32, the u32 itself is only written once
+            while remaining != 0 {
+                let bit_index_in_u32 = remaining.trailing_zeros();
+                remaining &= remaining - 1;
+
+                if !f(u32_index_in_bits_vec as u32 * 32 + bit_index_in_u32) {
+                    *bucket &= !(0b1 << bit_index_in_u32);
+                }
+            }
+        }
+    }
+
+    /// Removes all values from this bitmap that are present in `other`, without reallocating.
+    pub fn difference_with(&mut self, other: &SimpleBitmap) {
+        // u32s beyond the end of other have nothing to clear
+        let common_len = cmp::min(self.bits.len(), other.bits.len());
+        kernels::and_not_into(&mut self.bits[..common_len], &other.bits[..common_len]);
+    }
+
+    /// Returns the values present in this bitmap but not in `other`, i.e. `self & !other`, in a single pass over both
+    /// and without consuming either.
+    pub fn andnot(&self, other: &SimpleBitmap) -> SimpleBitmap {
+        // u32s of other are padded with zeros to the length of this bitmap's vec
+        let other_buckets = other.bits.iter().chain(iter::repeat(&0));
+
+        SimpleBitmap {
+            bits: iter::zip(&self.bits, other_buckets)
+                .map(|(left, right)| left & !right)
+                .collect(),
+        }
+    }
+
+    /// Removes all values from this bitmap that are present in `other`, the in-place form of
+    /// [SimpleBitmap::andnot]. Same as [SimpleBitmap::difference_with].
+    pub fn andnot_assign(&mut self, other: &SimpleBitmap) {
+        self.difference_with(other);
+    }
+
+    /// Returns the union of all given bitmaps. The result is allocated once, in contrast to chaining `|`, which
+    /// allocates for every intermediate union.
+    pub fn union_all<'a>(bitmaps: impl IntoIterator<Item = &'a SimpleBitmap>) -> SimpleBitmap {
+        let words: Vec<&[u32]> = bitmaps
+            .into_iter()
+            .map(|bitmap| bitmap.trimmed_bits())
+            .collect();
+
+        // the union needs as many u32s as the longest bitmap
+        let len = words.iter().map(|bits| bits.len()).max().unwrap_or(0);
+        let mut union = vec![0; len];
+
+        for bits in words {
+            kernels::or_into(&mut union[..bits.len()], bits);
+        }
+
+        SimpleBitmap { bits: union }
+    }
+
+    /// Returns the intersection of all given bitmaps, or an empty bitmap if none are given.
+    ///
+    /// The bitmaps are intersected u32 by u32, starting with the bitmap of the fewest values, and a u32 is skipped for
+    /// the remaining bitmaps as soon as it becomes zero.
+    pub fn intersect_all(bitmaps: &[&SimpleBitmap]) -> SimpleBitmap {
+        let mut by_count: Vec<(u64, &[u32])> = bitmaps
+            .iter()
+            .map(|bitmap| (bitmap.count_ones(), bitmap.trimmed_bits()))
+            .collect();
+        by_count.sort_unstable_by_key(|(count, _)| *count);
+
+        // the intersection can't be longer than the shortest bitmap
+        let len = by_count
+            .iter()
+            .map(|(_, bits)| bits.len())
+            .min()
+            .unwrap_or(0);
+
+        let intersection = (0..len)
+            .map(|u32_index_in_bits_vec| {
+                let mut bucket = u32::MAX;
+                for (_, bits) in &by_count {
+                    bucket &= bits[u32_index_in_bits_vec];
+                    if bucket == 0 {
+                        break;
+                    }
+                }
+
+                bucket
+            })
+            .collect();
+
+        SimpleBitmap { bits: intersection }
+    }
+}
+
+/// Memory usage of a [SimpleBitmap].
+///
+/// Created by [SimpleBitmap::memory_usage].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BitmapMemoryStats {
+    /// Bytes allocated for the u32s, including unused capacity.
+    pub allocated_bytes: usize,
+    /// Number of u32s in use.
+    pub words: usize,
+    /// Number of u32s at the end without any bit set, which [SimpleBitmap::shrink_to_fit] would release.
+    pub trailing_zero_words: usize,
+    /// Share of set bits among all bits of the u32s in use.
+    pub density: f64,
+}
+
+/// Sets the bits of `bits` moved `shift` positions up in `target`, which is extended as needed. Bits that would exceed
+/// u32::MAX are dropped.
+fn or_shifted_into(target: &mut Vec<u32>, bits: &[u32], shift: u32) {
+    if bits.is_empty() {
+        return;
+    }
+
+    let u32_shift = (shift / 32) as usize;
+    let bit_shift = shift & 0b11111;
+
+    let required_len = (bits.len() + u32_shift + 1).min(MAX_LEN);
+    if required_len > target.len() {
+        target.resize(required_len, 0);
+    }
+
+    // every u32 is split into the part staying within its target u32 and the part spilling into the next one
+    for (u32_index_in_bits_vec, bucket) in bits.iter().enumerate() {
+        let target_index = u32_index_in_bits_vec + u32_shift;
+        if target_index >= MAX_LEN {
+            break;
+        }
+
+        target[target_index] |= bucket << bit_shift;
+        if bit_shift != 0 && target_index + 1 < MAX_LEN {
+            target[target_index + 1] |= bucket >> (32 - bit_shift);
+        }
+    }
+}
+
+/// Counts the bits set within a range of indices of the u32s, missing u32s are treated as zero.
+fn count_ones_in(bits: &[u32], range: Range<u32>) -> u64 {
+    if range.is_empty() {
+        return 0;
+    }
+
+    let first_u32_index = (range.start / 32) as usize;
+    let last_u32_index = ((range.end - 1) / 32) as usize;
+    let first_mask = u32::MAX << (range.start & 0b11111);
+    let last_mask = u32::MAX >> (31 - ((range.end - 1) & 0b11111));
+
+    let count_masked = |u32_index_in_bits_vec: usize, mask: u32| {
+        bits.get(u32_index_in_bits_vec)
+            .map_or(0, |bucket| u64::from((bucket & mask).count_ones()))
+    };
+
+    if first_u32_index == last_u32_index {
+        return count_masked(first_u32_index, first_mask & last_mask);
+    }
+
+    // only the first and the last u32 are partially covered, the u32s in between are counted as a whole
+    let middle = bits
+        .get(first_u32_index + 1..last_u32_index.min(bits.len()))
+        .map_or(0, kernels::count_ones);
+
+    count_masked(first_u32_index, first_mask) + middle + count_masked(last_u32_index, last_mask)
+}
+
+/// Splits a range of indices into the u32s it touches, together with a mask of the bits within each u32. Only the
+/// first and the last u32 can be partially covered, all u32s in between are fully covered by the range.
+pub(crate) fn range_masks(range: Range<u32>) -> impl Iterator<Item = (usize, u32)> {
+    let first_u32_index = (range.start / 32) as usize;
+    let last_u32_index = (range.end.saturating_sub(1) / 32) as usize;
+    let is_empty = range.is_empty();
+
+    (first_u32_index..=last_u32_index)
+        .filter(move |_| !is_empty)
+        .map(move |u32_index_in_bits_vec| {
+            let first_bit = if u32_index_in_bits_vec == first_u32_index {
+                range.start & 0b11111
+            } else {
+                0
+            };
+            let last_bit = if u32_index_in_bits_vec == last_u32_index {
+                (range.end - 1) & 0b11111
+            } else {
+                31
+            };
+
+            let mask = (u32::MAX << first_bit) & (u32::MAX >> (31 - last_bit));
+
+            (u32_index_in_bits_vec, mask)
+        })
+}
+
+/// Returns the index of the `k`-th set bit within a u32, counting from zero. `k` has to be lower than the number of
+/// set bits.
+pub(crate) fn select_in_u32(mut bucket: u32, k: u32) -> u32 {
+    // unset the lowest set bits until the k-th set bit is the lowest one
+    for _ in 0..k {
+        bucket &= bucket - 1;
+    }
+
+    bucket.trailing_zeros()
+}
+
+impl Bitmap for SimpleBitmap {
+    fn set(&mut self, index: u32) {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+        let bit_index_in_u32 = index & 0b11111;
+
+        // if there is too little u32s in the bits vec, it has to be extended
+        if u32_index_in_bits_vec >= self.bits.len() {
+            self.bits.resize(u32_index_in_bits_vec + 1, 0);
+        }
+
+        let stored_u32 = self.bits[u32_index_in_bits_vec];
+
+        let modified_u32 = stored_u32 | (0b1 << bit_index_in_u32);
+
+        self.bits[u32_index_in_bits_vec] = modified_u32;
+    }
+
+    fn clear(&mut self, index: u32) {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+
+        // bits beyond the end of the bits vec are absent anyway, so the vec never has to be extended here
+        if let Some(bucket) = self.bits.get_mut(u32_index_in_bits_vec) {
+            let bit_index_in_u32 = index & 0b11111;
+
+            *bucket &= !(0b1 << bit_index_in_u32);
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+
+        if let Some(bucket) = self.bits.get(u32_index_in_bits_vec) {
+            let bit_index_in_u32 = index & 0b11111;
+
+            ((bucket >> bit_index_in_u32) & 0b1) == 1
+        } else {
+            false
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        kernels::count_ones(&self.bits)
+    }
+
+    fn insert(&mut self, index: u32) -> bool {
+        let u32_index_in_bits_vec = (index / 32) as usize;
+        let mask = 0b1 << (index & 0b11111);
+
+        // if there is too little u32s in the bits vec, it has to be extended
+        if u32_index_in_bits_vec >= self.bits.len() {
+            self.bits.resize(u32_index_in_bits_vec + 1, 0);
+        }
+
+        // the previous presence is read from the u32 that is modified anyway, so the index is only looked up once
+        let bucket = &mut self.bits[u32_index_in_bits_vec];
+        let absent = *bucket & mask == 0;
+        *bucket |= mask;
+
+        absent
+    }
+
+    fn remove(&mut self, index: u32) -> bool {
+        let mask = 0b1 << (index & 0b11111);
+
+        self.bits
+            .get_mut((index / 32) as usize)
+            .is_some_and(|bucket| {
+                let present = *bucket & mask != 0;
+                *bucket &= !mask;
+
+                present
+            })
+    }
+
+    fn is_empty(&self) -> bool {
+        SimpleBitmap::is_empty(self)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        SimpleBitmap::is_subset(self, other)
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        SimpleBitmap::is_disjoint(self, other)
+    }
+
+    fn intersection_len(&self, other: &Self) -> u64 {
+        SimpleBitmap::intersection_len(self, other)
+    }
+
+    fn union_len(&self, other: &Self) -> u64 {
+        SimpleBitmap::union_len(self, other)
+    }
+
+    fn difference_len(&self, other: &Self) -> u64 {
+        SimpleBitmap::difference_len(self, other)
+    }
+
+    // the set operations combine whole u32s instead of going value by value
+    fn union_with(&mut self, other: &Self) {
+        *self |= other;
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        *self &= other;
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        SimpleBitmap::difference_with(self, other);
+    }
+}
+
+impl FromIterator<u32> for SimpleBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bits = Vec::new();
+
+        // the u32 that is currently written to is kept outside of the vec, so that sorted input only touches the vec
+        // once per u32 instead of once per index
+        let mut current_u32_index_in_bits_vec = 0;
+        let mut current_u32 = 0;
+
+        for index in iter {
+            let u32_index_in_bits_vec = (index / 32) as usize;
+            let bit_index_in_u32 = index & 0b11111;
+
+            if u32_index_in_bits_vec != current_u32_index_in_bits_vec {
+                store_u32(&mut bits, current_u32_index_in_bits_vec, current_u32);
+
+                current_u32_index_in_bits_vec = u32_index_in_bits_vec;
+                current_u32 = bits.get(u32_index_in_bits_vec).copied().unwrap_or(0);
+            }
+
+            current_u32 |= 0b1 << bit_index_in_u32;
+        }
+
+        store_u32(&mut bits, current_u32_index_in_bits_vec, current_u32);
+
+        SimpleBitmap { bits }
+    }
+}
+
+impl Extend<u32> for SimpleBitmap {
+    /// Sets the given indices, which don't have to be sorted or unique.
+    ///
+    /// The highest index is only known once the iterator is exhausted, so the bits vec is reserved for the lower bound
+    /// of the size hint and grows whenever a higher index appears. [SimpleBitmap::extend_from_slice] finds the highest
+    /// index first and extends the bits vec exactly once.
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+
+        // n distinct indices need at least n bits, so at least that many u32s can be reserved upfront
+        let min_len = iter.size_hint().0.div_ceil(32);
+        self.bits.reserve(min_len.saturating_sub(self.bits.len()));
+
+        for index in iter {
+            let u32_index_in_bits_vec = (index / 32) as usize;
+
+            // if there is too little u32s in the bits vec, it has to be extended
+            if u32_index_in_bits_vec >= self.bits.len() {
+                self.bits.resize(u32_index_in_bits_vec + 1, 0);
+            }
+
+            self.bits[u32_index_in_bits_vec] |= 0b1 << (index & 0b11111);
+        }
+    }
+}
+
+impl From<&[u32]> for SimpleBitmap {
+    /// Creates a bitmap in which the given indices are present. They don't have to be sorted or unique.
+    fn from(indices: &[u32]) -> Self {
+        let mut bitmap = SimpleBitmap::new();
+        bitmap.extend_from_slice(indices);
+
+        bitmap
+    }
+}
+
+impl From<Vec<u32>> for SimpleBitmap {
+    /// Creates a bitmap in which the given indices are present. They don't have to be sorted or unique.
+    fn from(indices: Vec<u32>) -> Self {
+        SimpleBitmap::from(indices.as_slice())
+    }
+}
+
+impl From<&SimpleBitmap> for Vec<u32> {
+    /// Returns the indices of the present values, in ascending order.
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        let mut indices = Vec::with_capacity(bitmap.count_ones() as usize);
+        indices.extend(bitmap);
+
+        indices
+    }
+}
+
+impl From<SimpleBitmap> for Vec<u32> {
+    /// Returns the indices of the present values, in ascending order.
+    fn from(bitmap: SimpleBitmap) -> Self {
+        Vec::from(&bitmap)
+    }
+}
+
+impl From<BTreeSet<u32>> for SimpleBitmap {
+    fn from(indices: BTreeSet<u32>) -> Self {
+        let Some(max_index) = indices.last() else {
+            return SimpleBitmap::new();
+        };
+
+        // the set is sorted, so its last index tells the final length of the bits vec, which is then written from
+        // front to back
+        let mut bits = vec![0; (max_index / 32) as usize + 1];
+        for index in indices {
+            bits[(index / 32) as usize] |= 0b1 << (index & 0b11111);
+        }
+
+        SimpleBitmap { bits }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BuildHasher> From<HashSet<u32, S>> for SimpleBitmap {
+    fn from(indices: HashSet<u32, S>) -> Self {
+        let mut bitmap = SimpleBitmap::new();
+        bitmap.extend(indices);
+
+        bitmap
+    }
+}
+
+impl From<&SimpleBitmap> for BTreeSet<u32> {
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        bitmap.iter().collect()
+    }
+}
+
+impl From<SimpleBitmap> for BTreeSet<u32> {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        BTreeSet::from(&bitmap)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BuildHasher + Default> From<&SimpleBitmap> for HashSet<u32, S> {
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        let mut indices =
+            HashSet::with_capacity_and_hasher(bitmap.count_ones() as usize, S::default());
+        indices.extend(bitmap);
+
+        indices
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BuildHasher + Default> From<SimpleBitmap> for HashSet<u32, S> {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        HashSet::from(&bitmap)
+    }
+}
+
+/// Stores a u32 at the given index of the bits vec, extending the vec if there is too little u32s in it. Zeros are
+/// not stored explicitly.
+fn store_u32(bits: &mut Vec<u32>, u32_index_in_bits_vec: usize, value: u32) {
+    if value == 0 {
+        return;
+    }
+
+    if u32_index_in_bits_vec >= bits.len() {
+        bits.resize(u32_index_in_bits_vec + 1, 0);
+    }
+
+    bits[u32_index_in_bits_vec] = value;
+}
+
+impl BitOr for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        // the longer vec already holds enough u32s for the union, so the shorter one is merged into it in place
+        let (mut union, shorter) = if self.bits.len() >= rhs.bits.len() {
+            (self.bits, rhs.bits)
+        } else {
+            (rhs.bits, self.bits)
+        };
+
+        // u32s of the longer vec beyond the end of the shorter one are or-ed with implicit zeros and stay as they are
+        kernels::or_into(&mut union[..shorter.len()], &shorter);
+
+        SimpleBitmap { bits: union }
+    }
+}
+
+impl BitOrAssign<&SimpleBitmap> for SimpleBitmap {
+    fn bitor_assign(&mut self, rhs: &SimpleBitmap) {
+        // if there is too little u32s in the bits vec, it has to be extended so that it can hold all bits of rhs
+        if rhs.bits.len() > self.bits.len() {
+            self.bits.resize(rhs.bits.len(), 0);
+        }
+
+        kernels::or_into(&mut self.bits[..rhs.bits.len()], &rhs.bits);
+    }
+}
+
+impl BitOrAssign for SimpleBitmap {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self |= &rhs;
+    }
+}
+
+impl BitAnd for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        // bits beyond the shorter vec can never be set in both bitmaps, so the intersection never needs more
+        // u32s than the shorter of both vecs
+        self &= &rhs;
+        self
+    }
+}
+
+impl BitXor for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let (mut symmetric_difference, shorter) = if self.bits.len() >= rhs.bits.len() {
+            (self.bits, rhs.bits)
+        } else {
+            (rhs.bits, self.bits)
+        };
+
+        // the remaining u32s of the longer vec are xor-ed with implicit zeros and stay as they are
+        kernels::xor_into(&mut symmetric_difference[..shorter.len()], &shorter);
+
+        SimpleBitmap {
+            bits: symmetric_difference,
+        }
+    }
+}
+
+impl BitAndAssign<&SimpleBitmap> for SimpleBitmap {
+    fn bitand_assign(&mut self, rhs: &SimpleBitmap) {
+        // u32s beyond the end of rhs can never be set in both bitmaps, so they are dropped
+        self.bits.truncate(rhs.bits.len());
+
+        let common_len = self.bits.len();
+        kernels::and_into(&mut self.bits, &rhs.bits[..common_len]);
+    }
+}
+
+impl BitAndAssign for SimpleBitmap {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self &= &rhs;
+    }
+}
+
+impl Sub for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut difference = self.bits;
+
+        // the difference can never be longer than the left vec, so clearing the bits of rhs in place is enough.
+        // u32s of the left vec without a counterpart in rhs stay untouched
+        let common_len = cmp::min(difference.len(), rhs.bits.len());
+        kernels::and_not_into(&mut difference[..common_len], &rhs.bits[..common_len]);
+
+        SimpleBitmap { bits: difference }
+    }
+}
+
+/// Number of u32s needed to hold every index up to u32::MAX.
+pub(crate) const MAX_LEN: usize = (u32::MAX / 32) as usize + 1;
+
+/// Rejects decoded payloads of more bytes than the u32s needed to hold every index up to u32::MAX, as the values of
+/// the u32s beyond could not be addressed.
+pub(crate) fn check_payload_len(bytes: u64) -> Result<(), DecodeError> {
+    if bytes > 4 * MAX_LEN as u64 {
+        Err(DecodeError::Invalid(
+            "words exceed the index space of a u32",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+impl Shl<u32> for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    /// Moves every present value `rhs` positions up. Values that would exceed u32::MAX are dropped.
+    fn shl(self, rhs: u32) -> Self::Output {
+        let mut shifted = Vec::new();
+        or_shifted_into(&mut shifted, self.trimmed_bits(), rhs);
+
+        SimpleBitmap { bits: shifted }
+    }
+}
+
+impl Shr<u32> for SimpleBitmap {
+    type Output = SimpleBitmap;
+
+    /// Moves every present value `rhs` positions down. Values that would fall below zero are dropped.
+    fn shr(self, rhs: u32) -> Self::Output {
+        let u32_shift = (rhs / 32) as usize;
+        let bit_shift = rhs & 0b11111;
+
+        let bits = self.bits.get(u32_shift..).unwrap_or_default();
+
+        // every u32 is assembled from the upper part of its source u32 and the lower part of the one after
+        let shifted = (0..bits.len())
+            .map(|u32_index_in_bits_vec| {
+                let lower = bits[u32_index_in_bits_vec] >> bit_shift;
+                let upper = match bits.get(u32_index_in_bits_vec + 1) {
+                    Some(next) if bit_shift != 0 => next << (32 - bit_shift),
+                    _ => 0,
+                };
+
+                lower | upper
+            })
+            .collect();
+
+        SimpleBitmap { bits: shifted }
+    }
+}
+
+impl ShlAssign<u32> for SimpleBitmap {
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = core::mem::replace(self, SimpleBitmap::new()) << rhs;
+    }
+}
+
+impl ShrAssign<u32> for SimpleBitmap {
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = core::mem::replace(self, SimpleBitmap::new()) >> rhs;
+    }
+}
+
+impl PartialEq for SimpleBitmap {
+    fn eq(&self, other: &Self) -> bool {
+        // bitmaps with the same values are equal, no matter how many trailing zero u32s they carry
+        self.trimmed_bits() == other.trimmed_bits()
+    }
+}
+
+impl Eq for SimpleBitmap {}
+
+impl Hash for SimpleBitmap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // trailing zero u32s are skipped, so that equal bitmaps always produce equal hashes
+        self.trimmed_bits().hash(state);
+    }
+}
+
+impl Debug for SimpleBitmap {
+    /// Shows summary statistics of the bitmap. The alternate form `{:#?}` additionally dumps all u32s in binary.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let count = self.count_ones();
+        let density = self.density();
+
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("SimpleBitmap");
+        debug
+            .field("count", &count)
+            .field("min", &self.first_set())
+            .field("max", &self.last_set())
+            .field("words", &self.bits.len())
+            .field("density", &density);
+
+        if alternate {
+            debug.field("bits", &BinaryWords(&self.bits));
+        }
+
+        debug.finish()
+    }
+}
+
+/// Lists u32s in binary, from the lowest to the highest bit position.
+pub(crate) struct BinaryWords<'a>(pub(crate) &'a [u32]);
+
+impl Debug for BinaryWords<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        for bucket in self.0 {
+            list.entry(&format_args!("{bucket:032b}"));
+        }
+
+        list.finish()
+    }
+}
+
+impl Display for SimpleBitmap {
+    /// Lists the present values in ascending order, with runs of consecutive values compressed into inclusive ranges,
+    /// like `1, 6, 9-20, 42`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (position, run) in self.runs_inclusive().enumerate() {
+            if position > 0 {
+                f.write_str(", ")?;
+            }
+
+            if run.start() == run.end() {
+                write!(f, "{}", run.start())?;
+            } else {
+                write!(f, "{}-{}", run.start(), run.end())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn get_bits_vector_index() {
+        assert_eq!(31 / 32, 0);
+        assert_eq!(32 / 32, 1);
+    }
+
+    #[test]
+    #[allow(clippy::identity_op)]
+    fn get_bit_index_in_u32_using_modulo() {
+        assert_eq!(31 % 32, 31);
+        assert_eq!(32 % 32, 0);
+    }
+
+    #[test]
+    fn get_bit_index_in_u32_using_bitwise_and() {
+        assert_eq!(31 & 31, 31);
+        assert_eq!(32 & 31, 0);
+    }
+
+    #[test]
+    fn it_sets_and_gets_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+
+        // when
+        bm.set(31);
+        bm.set(32);
+        println!("{:?}", bm);
+
+        // then
+        assert!(!bm.get(0));
+        assert!(bm.get(31));
+        assert!(bm.get(32));
+    }
+
+    #[test]
+    fn it_clears_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(31);
+        bm.set(32);
+
+        // when
+        bm.clear(32);
+        bm.clear(0);
+        bm.clear(1000);
+        println!("{:?}", bm);
+
+        // then
+        assert!(bm.get(31));
+        assert!(!bm.get(32));
+        assert!(!bm.get(0));
+        assert!(!bm.get(1000));
+        assert_eq!(bm.bits.len(), 2);
+    }
+
+    #[test]
+    fn it_toggles_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(3);
+
+        // when
+        let toggled_3 = bm.toggle(3);
+        let toggled_40 = bm.toggle(40);
+
+        // then
+        assert!(!toggled_3);
+        assert!(!bm.get(3));
+        assert!(toggled_40);
+        assert!(bm.get(40));
+        assert!(!bm.toggle(40));
+        assert!(!bm.get(40));
+    }
+
+    #[test]
+    fn it_counts_set_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        assert_eq!(bm.count_ones(), 0);
+
+        // when
+        bm.set(0);
+        bm.set(31);
+        bm.set(32);
+        bm.set(1000);
+        bm.clear(31);
+
+        // then
+        assert_eq!(bm.count_ones(), 3);
+    }
+
+    #[test]
+    fn it_checks_for_emptiness() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        assert!(bm.is_empty());
+
+        // when
+        bm.set(40);
+
+        // then
+        assert!(!bm.is_empty());
+        bm.clear(40);
+        assert!(bm.is_empty());
+    }
+
+    #[test]
+    fn it_finds_the_first_set_bit() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        assert_eq!(bm.first_set(), None);
+
+        // when
+        bm.set(70);
+        bm.set(40);
+        bm.set(1000);
+
+        // then
+        assert_eq!(bm.first_set(), Some(40));
+        bm.clear(40);
+        assert_eq!(bm.first_set(), Some(70));
+    }
+
+    #[test]
+    fn it_finds_the_last_set_bit() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        assert_eq!(bm.last_set(), None);
+
+        // when
+        bm.set(0);
+        bm.set(40);
+        bm.set(1000);
+
+        // then
+        assert_eq!(bm.last_set(), Some(1000));
+        bm.clear(1000);
+        assert_eq!(bm.last_set(), Some(40));
+    }
+
+    #[test]
+    fn it_finds_the_nearest_set_bits_from_a_position() {
+        // given
+        let bm: SimpleBitmap = [3, 31, 32, 1000].into_iter().collect();
+
+        // then
+        assert_eq!(bm.next_set_bit(0), Some(3));
+        assert_eq!(bm.next_set_bit(3), Some(3));
+        assert_eq!(bm.next_set_bit(4), Some(31));
+        assert_eq!(bm.next_set_bit(33), Some(1000));
+        assert_eq!(bm.next_set_bit(1001), None);
+        assert_eq!(bm.prev_set_bit(u32::MAX), Some(1000));
+        assert_eq!(bm.prev_set_bit(999), Some(32));
+        assert_eq!(bm.prev_set_bit(31), Some(31));
+        assert_eq!(bm.prev_set_bit(30), Some(3));
+        assert_eq!(bm.prev_set_bit(2), None);
+    }
+
+    #[test]
+    fn it_finds_the_next_clear_bit_from_a_position() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set_range(0..70);
+        bm.clear(40);
+
+        // then
+        assert_eq!(bm.next_clear_bit(0), Some(40));
+        assert_eq!(bm.next_clear_bit(41), Some(70));
+        assert_eq!(bm.next_clear_bit(70), Some(70));
+        assert_eq!(bm.next_clear_bit(5_000), Some(5_000));
+        assert_eq!(SimpleBitmap::new().next_clear_bit(u32::MAX), Some(u32::MAX));
+    }
+
+    #[test]
+    fn it_counts_the_values_within_a_range() {
+        // given
+        let mut bm: SimpleBitmap = [3, 31, 1_000].into_iter().collect();
+        bm.set_range(200..500);
+
+        // then
+        assert_eq!(bm.count_ones_in_range(0..u32::MAX), 303);
+        assert_eq!(bm.count_ones_in_range(3..32), 2);
+        assert_eq!(bm.count_ones_in_range(4..31), 0);
+        assert_eq!(bm.count_ones_in_range(31..1_000), 301);
+        assert_eq!(bm.count_ones_in_range(250..260), 10);
+        assert_eq!(bm.count_ones_in_range(1_001..5_000), 0);
+        assert_eq!(bm.count_ones_in_range(40..40), 0);
+    }
+
+    #[test]
+    fn it_pops_the_lowest_and_highest_values() {
+        // given
+        let mut free_slots: SimpleBitmap = [3, 40, 1000].into_iter().collect();
+
+        // then
+        assert_eq!(free_slots.pop_first(), Some(3));
+        assert_eq!(free_slots.pop_last(), Some(1000));
+        assert_eq!(free_slots.pop_first(), Some(40));
+        assert_eq!(free_slots.pop_last(), None);
+        assert!(free_slots.is_empty());
+    }
+
+    #[test]
+    fn it_retains_the_values_satisfying_the_predicate() {
+        // given
+        let mut bm: SimpleBitmap = (0..100).collect();
+
+        // when
+        bm.retain(|index| index % 3 == 0 && index != 33);
+
+        // then
+        assert_eq!(bm.count_ones(), 33);
+        assert!(bm.get(99));
+        assert!(!bm.get(33));
+        assert!(!bm.get(98));
+    }
+
+    #[test]
+    fn it_reports_whether_inserting_and_removing_changed_the_bitmap() {
+        // given
+        let mut bm = SimpleBitmap::new();
+
+        // then
+        assert!(bm.insert(40));
+        assert!(!bm.insert(40));
+        assert!(bm.get(40));
+        assert!(bm.remove(40));
+        assert!(!bm.remove(40));
+        assert!(!bm.remove(1_000));
+        assert!(bm.is_empty());
+    }
+
+    #[test]
+    fn it_collects_indices_into_a_bitmap() {
+        // given
+        let sorted = (0..1000).filter(|index| index % 7 == 0);
+        let unsorted = [1000, 3, 64, 3, 31, 0];
+
+        // when
+        let sorted_bm: SimpleBitmap = sorted.clone().collect();
+        let unsorted_bm: SimpleBitmap = unsorted.into_iter().collect();
+
+        // then
+        assert_eq!(
+            sorted_bm.iter().collect::<Vec<u32>>(),
+            sorted.collect::<Vec<u32>>()
+        );
+        assert_eq!(
+            unsorted_bm.iter().collect::<Vec<u32>>(),
+            vec![0, 3, 31, 64, 1000]
+        );
+        assert!(SimpleBitmap::from_iter([]).is_empty());
+    }
+
+    #[test]
+    fn it_extends_a_bitmap_with_indices() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(5);
+
+        // when
+        bm.extend([1000, 3, 64]);
+        bm.extend([]);
+
+        // then
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![3, 5, 64, 1000]);
+        assert_eq!(bm.bits.len(), 32);
+    }
+
+    #[test]
+    fn it_extends_a_bitmap_from_a_slice_at_once() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(5);
+
+        // when
+        bm.extend_from_slice(&[1000, 3, 64, 3]);
+        let capacity = bm.bits.capacity();
+        bm.extend_from_slice(&[999, 0]);
+        bm.extend_from_slice(&[]);
+
+        // then
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            vec![0, 3, 5, 64, 999, 1000]
+        );
+        assert_eq!(bm.bits.len(), 32);
+        assert_eq!(bm.bits.capacity(), capacity);
+    }
+
+    #[test]
+    fn it_unions_all_bitmaps_at_once() {
+        // given
+        let bitmaps: Vec<SimpleBitmap> = (0..200)
+            .map(|offset| (offset..offset + 1_000).step_by(200).collect())
+            .collect();
+
+        // when
+        let union = SimpleBitmap::union_all(&bitmaps);
+
+        // then
+        assert_eq!(union, (0..1_000).collect());
+        assert_eq!(union.bits.len(), 32);
+        assert_eq!(SimpleBitmap::union_all([]), SimpleBitmap::new());
+    }
+
+    #[test]
+    fn it_intersects_all_bitmaps_at_once() {
+        // given
+        let multiples_of_2: SimpleBitmap = (0..1_000).step_by(2).collect();
+        let multiples_of_3: SimpleBitmap = (0..2_000).step_by(3).collect();
+        let few: SimpleBitmap = [0, 6, 7, 500].into_iter().collect();
+
+        // when
+        let intersection = SimpleBitmap::intersect_all(&[&multiples_of_2, &multiples_of_3, &few]);
+
+        // then
+        assert_eq!(intersection.iter().collect::<Vec<u32>>(), vec![0, 6]);
+        assert_eq!(intersection.bits.len(), 16);
+        assert_eq!(SimpleBitmap::intersect_all(&[]), SimpleBitmap::new());
+    }
+
+    #[test]
+    fn it_shifts_values_across_u32_boundaries() {
+        // given
+        let bm: SimpleBitmap = [0, 5, 31, 40].into_iter().collect();
+
+        // when
+        let mut shifted_down = bm.clone();
+        shifted_down >>= 6;
+
+        // then
+        assert_eq!(
+            (bm.clone() << 1).iter().collect::<Vec<u32>>(),
+            vec![1, 6, 32, 41]
+        );
+        assert_eq!(
+            (bm.clone() << 64).iter().collect::<Vec<u32>>(),
+            vec![64, 69, 95, 104]
+        );
+        assert_eq!(shifted_down.iter().collect::<Vec<u32>>(), vec![25, 34]);
+        assert_eq!(bm.clone() >> 32, [8].into_iter().collect());
+        assert_eq!(bm.clone() >> 41, SimpleBitmap::new());
+        assert_eq!(bm << 0 >> 0, [0, 5, 31, 40].into_iter().collect());
+    }
+
+    #[test]
+    fn it_complements_values_below_the_universe() {
+        // given
+        let bm: SimpleBitmap = [1, 3, 100].into_iter().collect();
+
+        // when
+        let complement = bm.complement(6);
+
+        // then
+        assert_eq!(complement.iter().collect::<Vec<u32>>(), vec![0, 2, 4, 5]);
+        assert_eq!(bm.complement(64).count_ones(), 62);
+        assert_eq!(bm.complement(0), SimpleBitmap::new());
+        assert_eq!(SimpleBitmap::new().complement(40), (0..40).collect());
+    }
+
+    #[test]
+    fn it_splits_and_joins_bitmaps_at_an_index() {
+        // given
+        let bm: SimpleBitmap = [0, 31, 32, 99, 100, 1_000].into_iter().collect();
+
+        // when
+        let (lower, upper) = bm.split_at(99);
+        let mut joined = lower.clone();
+        joined.append_at(99, &upper);
+
+        // then
+        assert_eq!(lower.iter().collect::<Vec<u32>>(), vec![0, 31, 32]);
+        assert_eq!(upper.iter().collect::<Vec<u32>>(), vec![0, 1, 901]);
+        assert_eq!(joined, bm);
+        assert_eq!(bm.split_at(0), (SimpleBitmap::new(), bm.clone()));
+    }
+
+    #[test]
+    fn it_finds_the_nth_and_the_highest_values() {
+        // given
+        let bm: SimpleBitmap = [2, 40, 41, 1_000].into_iter().collect();
+
+        // then
+        assert_eq!(bm.nth(0), Some(2));
+        assert_eq!(bm.nth(3), Some(1_000));
+        assert_eq!(bm.nth(4), None);
+        assert_eq!(bm.top_k(3), vec![1_000, 41, 40]);
+        assert_eq!(bm.top_k(10), vec![1_000, 41, 40, 2]);
+        assert!(bm.top_k(0).is_empty());
+    }
+
+    #[test]
+    fn it_displays_values_in_range_notation() {
+        // given
+        let mut bm: SimpleBitmap = [1, 6, 42, 43].into_iter().collect();
+        bm.set_range(9..21);
+
+        // then
+        assert_eq!(bm.to_string(), "1, 6, 9-20, 42-43");
+        assert_eq!(bm.to_range_string(), bm.to_string());
+        assert_eq!(SimpleBitmap::new().to_string(), "");
+    }
+
+    #[test]
+    fn it_summarizes_bitmaps_in_debug_output() {
+        // given
+        let bm: SimpleBitmap = [1, 40].into_iter().collect();
+
+        // then
+        assert_eq!(
+            format!("{bm:?}"),
+            "SimpleBitmap { count: 2, min: Some(1), max: Some(40), words: 2, density: 0.03125 }"
+        );
+        assert!(format!("{bm:#?}").contains("00000000000000000000000100000000"));
+        assert!(!format!("{bm:?}").contains("bits"));
+    }
+
+    #[test]
+    fn it_reports_its_memory_usage() {
+        // given
+        let mut bm = SimpleBitmap::with_capacity(255);
+        bm.set_range(0..32);
+        bm.set(100);
+        bm.clear(100);
 
-/// Non-optimized implementation of a [Bitmap].
-#[derive(Clone)]
-pub struct SimpleBitmap {
-    bits: Vec<u32>,
-}
+        // when
+        let stats = bm.memory_usage();
 
-impl SimpleBitmap {
-    pub fn new() -> Self {
-        Self { bits: Vec::new() }
+        // then
+        // Vec may allocate more than the reserved capacity, so only a lower bound of the allocation is known
+        assert!(stats.allocated_bytes >= 8 * 4);
+        assert_eq!(stats.words, 4);
+        assert_eq!(stats.trailing_zero_words, 3);
+        assert_eq!(stats.density, 0.25);
     }
-}
 
-impl Bitmap for SimpleBitmap {
-    fn set(&mut self, index: u32) {
-        let u32_index_in_bits_vec = (index / 32) as usize;
-        let bit_index_in_u32 = index & 0b11111;
+    #[test]
+    fn it_converts_from_and_to_index_vecs() {
+        // given
+        let indices = vec![64, 3, 1000, 3];
 
-        // if there is too little u32s in the bits vec, it has to be extended
-        if u32_index_in_bits_vec >= self.bits.len() {
-            self.bits.resize(u32_index_in_bits_vec + 1, 0);
-        }
+        // when
+        let from_vec = SimpleBitmap::from(indices.clone());
+        let from_slice = SimpleBitmap::from(&indices[..2]);
 
-        let stored_u32 = self.bits[u32_index_in_bits_vec];
+        // then
+        assert_eq!(Vec::from(&from_vec), vec![3, 64, 1000]);
+        assert_eq!(Vec::<u32>::from(from_slice), vec![3, 64]);
+        assert_eq!(Vec::from(SimpleBitmap::new()), Vec::<u32>::new());
+    }
 
-        let modified_u32 = stored_u32 | (0b1 << bit_index_in_u32);
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_converts_from_and_to_sets() {
+        // given
+        let btree_set = BTreeSet::from([3, 64, 1000]);
+        let hash_set: HashSet<u32> = HashSet::from([3, 64, 1000]);
 
-        self.bits[u32_index_in_bits_vec] = modified_u32;
+        // when
+        let from_btree_set = SimpleBitmap::from(btree_set.clone());
+        let from_hash_set = SimpleBitmap::from(hash_set.clone());
+
+        // then
+        assert_eq!(from_btree_set, from_hash_set);
+        assert_eq!(from_btree_set.bits.len(), 32);
+        assert_eq!(BTreeSet::from(&from_hash_set), btree_set);
+        assert_eq!(HashSet::<u32>::from(from_btree_set), hash_set);
+        assert_eq!(SimpleBitmap::from(BTreeSet::new()), SimpleBitmap::new());
     }
 
-    fn get(&self, index: u32) -> bool {
-        let u32_index_in_bits_vec = (index / 32) as usize;
+    #[test]
+    fn it_sets_ranges() {
+        // given
+        let mut bm = SimpleBitmap::new();
 
-        if let Some(bucket) = self.bits.get(u32_index_in_bits_vec) {
-            let bit_index_in_u32 = index & 0b11111;
+        // when
+        bm.set_range(3..7);
+        bm.set_range(30..100);
+        bm.set_range(200..200);
 
-            ((bucket >> bit_index_in_u32) & 0b1) == 1
-        } else {
-            false
-        }
+        // then
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            (3..7).chain(30..100).collect::<Vec<u32>>()
+        );
+        assert_eq!(bm.bits.len(), 4);
+        assert_eq!(bm.bits[1], u32::MAX);
     }
-}
 
-impl BitOr for SimpleBitmap {
-    type Output = SimpleBitmap;
+    #[test]
+    fn it_clears_ranges() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set_range(0..100);
 
-    fn bitor(self, rhs: Self) -> Self::Output {
-        // allocate enough capacity for the larger of both vecs
-        let mut union = Vec::with_capacity(cmp::max(self.bits.len(), rhs.bits.len()));
+        // when
+        bm.clear_range(10..70);
+        bm.clear_range(90..1000);
 
-        let mut left_iter = self.bits.iter();
-        let mut right_iter = rhs.bits.iter();
+        // then
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            (0..10).chain(70..90).collect::<Vec<u32>>()
+        );
+        assert_eq!(bm.bits.len(), 4);
+    }
 
-        // iterate over both iterators and perform the bitwise or operation as long as both iters yield u32s and
-        // add the result to the union vector
-        for (left, right) in iter::zip(&mut left_iter, &mut right_iter) {
-            union.push(left | right);
-        }
+    #[test]
+    fn it_flips_ranges() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set_range(0..10);
 
-        // if there is u32s remaining in left, add the u32s to the union vector
-        for left in left_iter {
-            union.push(*left);
-        }
+        // when
+        bm.flip_range(5..40);
 
-        // if there is u32s remaining in right, add the u32s to the union vector
-        for right in right_iter {
-            union.push(*right);
-        }
+        // then
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            (0..5).chain(10..40).collect::<Vec<u32>>()
+        );
+    }
 
-        SimpleBitmap { bits: union }
+    #[test]
+    fn it_checks_whether_ranges_are_contained() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set_range(10..100);
+
+        // then
+        assert!(bm.contains_range(10..100));
+        assert!(bm.contains_range(40..50));
+        assert!(bm.contains_range(1000..1000));
+        assert!(!bm.contains_range(9..20));
+        assert!(!bm.contains_range(90..101));
+        assert!(!bm.contains_range(500..600));
     }
-}
 
-impl Debug for SimpleBitmap {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for num in &self.bits {
-            writeln!(f, "{:032b}", num)?;
-        }
-        Ok(())
+    #[test]
+    fn it_compares_bitmaps_by_their_values() {
+        // given
+        let mut left = SimpleBitmap::new();
+        left.set(3);
+        left.set(1000);
+        left.clear(1000);
+
+        let mut right = SimpleBitmap::new();
+        right.set(3);
+
+        // then
+        assert_ne!(left.bits.len(), right.bits.len());
+        assert_eq!(left, right);
+        right.set(4);
+        assert_ne!(left, right);
+        assert_eq!(SimpleBitmap::new(), left - right);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
     #[test]
-    fn get_bits_vector_index() {
-        assert_eq!(31 / 32, 0);
-        assert_eq!(32 / 32, 1);
+    fn it_hashes_equal_bitmaps_equally() {
+        // given
+        let mut left = SimpleBitmap::new();
+        left.set(3);
+        left.set(1000);
+        left.clear(1000);
+
+        let mut right = SimpleBitmap::new();
+        right.set(3);
+
+        // when
+        let filters: std::collections::HashSet<SimpleBitmap> = [left, right].into_iter().collect();
+
+        // then
+        assert_eq!(filters.len(), 1);
     }
 
     #[test]
-    #[allow(clippy::identity_op)]
-    fn get_bit_index_in_u32_using_modulo() {
-        assert_eq!(31 % 32, 31);
-        assert_eq!(32 % 32, 0);
+    fn it_shrinks_to_fit() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(3);
+        bm.set(1000);
+        bm.clear(1000);
+
+        // when
+        bm.shrink_to_fit();
+
+        // then
+        assert_eq!(bm.bits.len(), 1);
+        assert_eq!(bm.bits.capacity(), 1);
+        assert!(bm.get(3));
     }
 
     #[test]
-    fn get_bit_index_in_u32_using_bitwise_and() {
-        assert_eq!(31 & 31, 31);
-        assert_eq!(32 & 31, 0);
+    fn it_preallocates_capacity() {
+        // given
+        let mut bm = SimpleBitmap::with_capacity(1000);
+        let capacity = bm.bits.capacity();
+
+        // when
+        bm.set(1000);
+        bm.set(3);
+
+        // then
+        assert!(capacity >= 32);
+        assert_eq!(bm.bits.capacity(), capacity);
+
+        bm.reserve(10_000);
+        assert!(bm.bits.capacity() >= 313);
+        assert!(bm.get(1000));
     }
 
     #[test]
-    fn it_sets_and_gets_bits() {
+    fn it_checks_for_subsets_and_supersets() {
+        // given
+        let mut permissions = SimpleBitmap::new();
+        permissions.set(1);
+        permissions.set(40);
+        permissions.set(70);
+
+        let mut required = SimpleBitmap::new();
+        required.set(1);
+        required.set(40);
+        required.set(1000);
+        required.clear(1000);
+
+        // then
+        assert!(required.is_subset(&permissions));
+        assert!(permissions.is_superset(&required));
+        assert!(!permissions.is_subset(&required));
+        assert!(SimpleBitmap::new().is_subset(&required));
+        required.set(2);
+        assert!(!required.is_subset(&permissions));
+    }
+
+    #[test]
+    fn it_checks_for_disjointness() {
+        // given
+        let mut booked = SimpleBitmap::new();
+        booked.set(1);
+        booked.set(40);
+
+        let mut requested = SimpleBitmap::new();
+        requested.set(2);
+        requested.set(41);
+        requested.set(1000);
+
+        // then
+        assert!(booked.is_disjoint(&requested));
+        assert!(requested.is_disjoint(&booked));
+        requested.set(40);
+        assert!(!booked.is_disjoint(&requested));
+    }
+
+    #[test]
+    fn it_counts_combinations_without_materializing_them() {
+        // given
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+        peated_bm.set(70);
+
+        // then
+        assert_eq!(speyside_bm.intersection_len(&peated_bm), 1);
+        assert_eq!(speyside_bm.union_len(&peated_bm), 5);
+        assert_eq!(peated_bm.union_len(&speyside_bm), 5);
+        assert_eq!(speyside_bm.difference_len(&peated_bm), 2);
+        assert_eq!(peated_bm.difference_len(&speyside_bm), 2);
+        assert_eq!(
+            speyside_bm.union_len(&peated_bm),
+            (speyside_bm.clone() | peated_bm.clone()).count_ones()
+        );
+    }
+
+    #[test]
+    fn it_ranks_indices() {
         // given
         let mut bm = SimpleBitmap::new();
+        bm.set(1);
+        bm.set(6);
+        bm.set(40);
+        bm.set(70);
 
-        // when
-        bm.set(31);
-        bm.set(32);
-        println!("{:?}", bm);
+        // then
+        assert_eq!(bm.rank(0), 0);
+        assert_eq!(bm.rank(1), 0);
+        assert_eq!(bm.rank(2), 1);
+        assert_eq!(bm.rank(40), 2);
+        assert_eq!(bm.rank(41), 3);
+        assert_eq!(bm.rank(u32::MAX), 4);
+    }
+
+    #[test]
+    fn it_selects_the_kth_set_bit() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(1);
+        bm.set(6);
+        bm.set(40);
+        bm.set(70);
 
         // then
-        assert!(!bm.get(0));
-        assert!(bm.get(31));
-        assert!(bm.get(32));
+        assert_eq!(bm.select(0), Some(1));
+        assert_eq!(bm.select(1), Some(6));
+        assert_eq!(bm.select(2), Some(40));
+        assert_eq!(bm.select(3), Some(70));
+        assert_eq!(bm.select(4), None);
+        assert_eq!(bm.select(bm.rank(40)), Some(40));
+    }
+
+    #[test]
+    fn it_computes_range_masks() {
+        assert_eq!(range_masks(0..32).collect::<Vec<_>>(), vec![(0, u32::MAX)]);
+        assert_eq!(range_masks(1..3).collect::<Vec<_>>(), vec![(0, 0b110)]);
+        assert_eq!(
+            range_masks(31..33).collect::<Vec<_>>(),
+            vec![(0, 0b1 << 31), (1, 0b1)]
+        );
+        assert_eq!(range_masks(5..5).count(), 0);
     }
 
     #[test]
@@ -156,4 +2003,220 @@ mod tests {
         assert!(speyside_or_highlands.get(9));
         assert!(!speyside_or_highlands.get(10));
     }
+
+    #[test]
+    fn it_keeps_all_u32s_of_the_longer_operand() {
+        // given
+        let mut long_bm = SimpleBitmap::new();
+        long_bm.set(1);
+        long_bm.set(32);
+        long_bm.set(64);
+
+        let mut short_bm = SimpleBitmap::new();
+        short_bm.set(2);
+
+        // when
+        let union = long_bm.clone() | short_bm.clone();
+        let symmetric_difference = long_bm ^ short_bm;
+
+        // then
+        assert_eq!(union.iter().collect::<Vec<u32>>(), vec![1, 2, 32, 64]);
+        assert_eq!(
+            symmetric_difference.iter().collect::<Vec<u32>>(),
+            vec![1, 2, 32, 64]
+        );
+    }
+
+    #[test]
+    fn it_builds_bit_intersections() {
+        // given
+        // Speyside    --> [0, 1, 0, 0, 0, 0, 1, 0, 0, 0, ..., 1]
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+        println!("{:?}", speyside_bm);
+
+        // Peated      --> [0, 1, 1, 0, 0, 0, 0, 0, 0, 0]
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+        println!("{:?}", peated_bm);
+
+        // when
+        let speyside_and_peated = speyside_bm & peated_bm;
+        println!("{:?}", speyside_and_peated);
+
+        // then
+        // Intersection --> [0, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+        assert!(!speyside_and_peated.get(0));
+        assert!(speyside_and_peated.get(1));
+        assert!(!speyside_and_peated.get(2));
+        assert!(!speyside_and_peated.get(6));
+        assert!(!speyside_and_peated.get(40));
+        assert_eq!(speyside_and_peated.bits.len(), 1);
+    }
+
+    #[test]
+    fn it_builds_bit_symmetric_differences() {
+        // given
+        // Speyside    --> [0, 1, 0, 0, 0, 0, 1, 0, 0, 0]
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+
+        // Peated      --> [0, 1, 1, 0, 0, 0, 0, 0, 0, 0, ..., 1]
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+        peated_bm.set(40);
+
+        // when
+        let speyside_xor_peated = speyside_bm ^ peated_bm;
+        println!("{:?}", speyside_xor_peated);
+
+        // then
+        // Symmetric difference --> [0, 0, 1, 0, 0, 0, 1, 0, 0, 0, ..., 1]
+        assert!(!speyside_xor_peated.get(0));
+        assert!(!speyside_xor_peated.get(1));
+        assert!(speyside_xor_peated.get(2));
+        assert!(speyside_xor_peated.get(6));
+        assert!(speyside_xor_peated.get(40));
+        assert!(!speyside_xor_peated.get(41));
+    }
+
+    #[test]
+    fn it_builds_bit_differences() {
+        // given
+        // Speyside    --> [0, 1, 0, 0, 0, 0, 1, 0, 0, 0, ..., 1]
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        // Peated      --> [0, 1, 1, 0, 0, 0, 0, 0, 0, 0, ..., 0, 1]
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+        peated_bm.set(70);
+
+        // when
+        let speyside_without_peated = speyside_bm - peated_bm;
+        println!("{:?}", speyside_without_peated);
+
+        // then
+        // Difference  --> [0, 0, 0, 0, 0, 0, 1, 0, 0, 0, ..., 1, 0]
+        assert!(!speyside_without_peated.get(1));
+        assert!(!speyside_without_peated.get(2));
+        assert!(speyside_without_peated.get(6));
+        assert!(speyside_without_peated.get(40));
+        assert!(!speyside_without_peated.get(70));
+    }
+
+    #[test]
+    fn it_builds_bit_unions_in_place() {
+        // given
+        let mut distilleries_bm = SimpleBitmap::new();
+        distilleries_bm.set(1);
+
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        let mut highlands_bm = SimpleBitmap::new();
+        highlands_bm.set(2);
+
+        // when
+        distilleries_bm |= &speyside_bm;
+        distilleries_bm |= highlands_bm;
+        println!("{:?}", distilleries_bm);
+
+        // then
+        assert!(!distilleries_bm.get(0));
+        assert!(distilleries_bm.get(1));
+        assert!(distilleries_bm.get(2));
+        assert!(distilleries_bm.get(6));
+        assert!(distilleries_bm.get(40));
+        assert!(!distilleries_bm.get(41));
+    }
+
+    #[test]
+    fn it_builds_bit_intersections_in_place() {
+        // given
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+
+        // when
+        speyside_bm &= &peated_bm;
+        println!("{:?}", speyside_bm);
+
+        // then
+        assert!(speyside_bm.get(1));
+        assert!(!speyside_bm.get(2));
+        assert!(!speyside_bm.get(6));
+        assert!(!speyside_bm.get(40));
+        assert_eq!(speyside_bm.bits.len(), 1);
+    }
+
+    #[test]
+    fn it_builds_bit_differences_in_place() {
+        // given
+        let mut speyside_bm = SimpleBitmap::new();
+        speyside_bm.set(1);
+        speyside_bm.set(6);
+        speyside_bm.set(40);
+
+        let mut peated_bm = SimpleBitmap::new();
+        peated_bm.set(1);
+        peated_bm.set(2);
+        peated_bm.set(70);
+
+        // when
+        speyside_bm.difference_with(&peated_bm);
+        println!("{:?}", speyside_bm);
+
+        // then
+        assert!(!speyside_bm.get(1));
+        assert!(!speyside_bm.get(2));
+        assert!(speyside_bm.get(6));
+        assert!(speyside_bm.get(40));
+        assert!(!speyside_bm.get(70));
+    }
+
+    #[test]
+    fn it_converts_into_other_implementations() {
+        // given
+        let bm: SimpleBitmap = [1, 6, 40, 70_000].into_iter().collect();
+
+        // when
+        let roaring: crate::RoaringBitmap = bm.convert();
+        let sparse: crate::SparseBitmap = roaring.convert();
+
+        // then
+        assert_eq!(roaring.count_ones(), 4);
+        assert_eq!(sparse.iter().collect::<Vec<u32>>(), vec![1, 6, 40, 70_000]);
+        assert_eq!(sparse.convert::<SimpleBitmap>(), bm);
+    }
+
+    #[test]
+    fn it_builds_and_not_in_one_pass() {
+        // given
+        let mut left: SimpleBitmap = [1, 6, 40, 1_000].into_iter().collect();
+        let right: SimpleBitmap = [6, 40, 70].into_iter().collect();
+
+        // when
+        let difference = left.andnot(&right);
+        left.andnot_assign(&right);
+
+        // then
+        assert_eq!(difference.iter().collect::<Vec<u32>>(), vec![1, 1_000]);
+        assert_eq!(left, difference);
+        assert_eq!(right.andnot(&difference), right);
+    }
 }
@@ -0,0 +1,259 @@
+use super::{SimpleBitmap, check_payload_len};
+use crate::BitmapRef;
+use crate::error::{ByteReader, DecodeError};
+use alloc::vec::Vec;
+
+/// Identifies the binary format of a [SimpleBitmap].
+const MAGIC: [u8; 4] = *b"BMAP";
+
+/// Version of the binary format written by [SimpleBitmap::to_bytes].
+const VERSION: u16 = 1;
+
+/// Size of the u32s written by [SimpleBitmap::to_bytes].
+const WORD_SIZE: u8 = 4;
+
+impl SimpleBitmap {
+    /// Serializes the bitmap into a self-describing binary format.
+    ///
+    /// All integers are little-endian on every target, so bytes written on a little-endian machine can be read on a
+    /// big-endian one and vice versa:
+    ///
+    /// | Bytes | Content                                     |
+    /// |-------|---------------------------------------------|
+    /// | 4     | magic number `BMAP`                         |
+    /// | 2     | format version, currently 1                 |
+    /// | 1     | size of a word in bytes, currently 4        |
+    /// | 1     | flags, currently always 0                   |
+    /// | 8     | number of words                             |
+    /// | ...   | words, bit `i` of word `j` holding the value `j * 8 * word size + i` |
+    ///
+    /// The header is 16 bytes long, so the words stay aligned to a u32 if the bytes are, which lets
+    /// [BitmapRef::from_bytes_zero_copy] borrow them.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let words = self.trimmed_bits();
+
+        let mut bytes = Vec::with_capacity(16 + 4 * words.len());
+        bytes.extend(MAGIC);
+        bytes.extend(VERSION.to_le_bytes());
+        bytes.push(WORD_SIZE);
+        bytes.push(0);
+        bytes.extend((words.len() as u64).to_le_bytes());
+        for bucket in words {
+            bytes.extend(bucket.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Deserializes a bitmap from the binary format written by [SimpleBitmap::to_bytes].
+    ///
+    /// Words of 1, 2, 4 and 8 bytes are accepted, so bitmaps written with a different word size can be read as well.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (_, payload) = read_header(bytes)?;
+
+        // little-endian words of any size lay out their bits in the same order, so the payload can be regrouped into
+        // u32s regardless of the word size it was written with
+        Ok(SimpleBitmap::from_le_bytes(payload))
+    }
+}
+
+impl<'a> BitmapRef<'a> {
+    /// Borrows the words of a bitmap in the binary format written by [SimpleBitmap::to_bytes] without copying them, so
+    /// large bitmaps, e.g. in a memory-mapped file, can be queried right away.
+    ///
+    /// The header is validated like by [SimpleBitmap::from_bytes]. In addition, the words must be 4 bytes long and
+    /// aligned to a u32, which they are if the bytes are, and the target must be little-endian.
+    pub fn from_bytes_zero_copy(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let (word_size, payload) = read_header(bytes)?;
+
+        if word_size != WORD_SIZE {
+            return Err(DecodeError::Invalid(
+                "only words of 4 bytes can be borrowed",
+            ));
+        }
+        if cfg!(target_endian = "big") {
+            return Err(DecodeError::Invalid(
+                "words can only be borrowed on little-endian targets",
+            ));
+        }
+
+        BitmapRef::from_bytes(payload).ok_or(DecodeError::Invalid("words are not aligned to a u32"))
+    }
+}
+
+/// Validates the header of the binary format and returns the size of the words together with the bytes of all words.
+fn read_header(bytes: &[u8]) -> Result<(u8, &[u8]), DecodeError> {
+    let mut reader = ByteReader::new(bytes);
+
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(DecodeError::UnknownFormat);
+    }
+
+    let version = reader.read_u16()?;
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+
+    let word_size = reader.take(1)?[0];
+    if !matches!(word_size, 1 | 2 | 4 | 8) {
+        return Err(DecodeError::Invalid("word size is not 1, 2, 4 or 8 bytes"));
+    }
+
+    if reader.take(1)?[0] != 0 {
+        return Err(DecodeError::Invalid("unknown flags are set"));
+    }
+
+    let payload_len =
+        reader
+            .read_u64()?
+            .checked_mul(u64::from(word_size))
+            .ok_or(DecodeError::Invalid(
+                "words exceed the index space of a u32",
+            ))?;
+    check_payload_len(payload_len)?;
+
+    let payload_len = usize::try_from(payload_len).map_err(|_| DecodeError::UnexpectedEnd)?;
+    let payload = reader.take(payload_len)?;
+    reader.finish()?;
+
+    Ok((word_size, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+
+    #[test]
+    fn it_writes_a_header_before_the_words() {
+        // given
+        let mut bm: SimpleBitmap = [0, 33, 100].into_iter().collect();
+        bm.clear(100);
+
+        // when
+        let bytes = bm.to_bytes();
+
+        // then
+        assert_eq!(
+            bytes,
+            vec![
+                b'B', b'M', b'A', b'P', // magic
+                1, 0, // version
+                4, // word size
+                0, // flags
+                2, 0, 0, 0, 0, 0, 0, 0, // number of words
+                1, 0, 0, 0, // first word
+                2, 0, 0, 0, // second word
+            ]
+        );
+        assert_eq!(SimpleBitmap::from_bytes(&bytes), Ok(bm));
+    }
+
+    #[test]
+    fn it_round_trips_golden_bytes_of_a_word_with_distinct_bytes() {
+        // given
+        let bm: SimpleBitmap = [0, 9, 18, 31].into_iter().collect();
+        let bytes = [
+            b'B', b'M', b'A', b'P', 1, 0, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0, // header
+            0x01, 0x02, 0x04,
+            0x80, // word 0x80040201, every byte differs so swapped bytes are noticed
+        ];
+
+        // then
+        assert_eq!(bm.to_bytes(), bytes);
+        assert_eq!(SimpleBitmap::from_bytes(&bytes), Ok(bm));
+    }
+
+    #[test]
+    fn it_reads_words_of_other_sizes() {
+        // given
+        let mut bytes = vec![b'B', b'M', b'A', b'P', 1, 0, 8, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        bytes.extend((0b1_u64 << 40 | 0b1).to_le_bytes());
+
+        // when
+        let bm = SimpleBitmap::from_bytes(&bytes).unwrap();
+
+        // then
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![0, 40]);
+    }
+
+    #[test]
+    fn it_rejects_unknown_formats_and_versions() {
+        // given
+        let bytes = [5].into_iter().collect::<SimpleBitmap>().to_bytes();
+        let mut future_version = bytes.clone();
+        future_version[4] = 2;
+
+        // then
+        assert_eq!(
+            SimpleBitmap::from_bytes(b"PAMB"),
+            Err(DecodeError::UnknownFormat)
+        );
+        assert_eq!(
+            SimpleBitmap::from_bytes(&future_version),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            SimpleBitmap::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    fn it_rejects_words_beyond_the_index_space_of_a_u32() {
+        // given
+        let header = |word_size: u8, words: u64| {
+            let mut bytes = vec![b'B', b'M', b'A', b'P', 1, 0, word_size, 0];
+            bytes.extend(words.to_le_bytes());
+            bytes
+        };
+        let max_words = (u64::from(u32::MAX) + 1) / 32;
+
+        // then
+        // the header is rejected before the missing words are noticed
+        let invalid = Err(DecodeError::Invalid(
+            "words exceed the index space of a u32",
+        ));
+        assert_eq!(SimpleBitmap::from_bytes(&header(4, max_words + 1)), invalid);
+        assert_eq!(
+            SimpleBitmap::from_bytes(&header(8, max_words / 2 + 1)),
+            invalid
+        );
+        assert_eq!(SimpleBitmap::from_bytes(&header(1, u64::MAX)), invalid);
+        assert_eq!(
+            BitmapRef::from_bytes_zero_copy(&header(4, max_words + 1)).err(),
+            invalid.err()
+        );
+        assert_eq!(
+            SimpleBitmap::from_bytes(&header(4, max_words)),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn it_borrows_the_words_of_aligned_bytes() {
+        // given
+        let bm: SimpleBitmap = [0, 33, 1_000].into_iter().collect();
+        let bytes = bm.to_bytes();
+
+        // the bytes are copied into u32s, so they are aligned
+        let mut words = vec![0_u32; bytes.len() / 4];
+        let aligned: &mut [u8] = unsafe { words.align_to_mut::<u8>().1 };
+        aligned.copy_from_slice(&bytes);
+
+        // when
+        let borrowed = BitmapRef::from_bytes_zero_copy(aligned).unwrap();
+
+        // then
+        assert_eq!(borrowed.iter().collect::<Vec<u32>>(), vec![0, 33, 1_000]);
+        assert!(core::ptr::eq(
+            borrowed.words().as_ptr().cast::<u8>(),
+            aligned[16..].as_ptr()
+        ));
+        assert_eq!(
+            BitmapRef::from_bytes_zero_copy(&aligned[..aligned.len() - 1]).unwrap_err(),
+            DecodeError::UnexpectedEnd
+        );
+    }
+}
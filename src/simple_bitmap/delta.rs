@@ -0,0 +1,58 @@
+use super::SimpleBitmap;
+
+/// Changes that turn one [SimpleBitmap] into another, e.g. to replicate updates without shipping the full bitmap.
+///
+/// Created by [SimpleBitmap::diff].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitmapDelta {
+    /// Values absent from the older bitmap but present in the newer one.
+    pub added: SimpleBitmap,
+    /// Values present in the older bitmap but absent from the newer one.
+    pub removed: SimpleBitmap,
+}
+
+impl BitmapDelta {
+    /// Returns `true` if both bitmaps hold the same values.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl SimpleBitmap {
+    /// Returns the changes from this bitmap to `newer`, which [SimpleBitmap::apply] replays.
+    pub fn diff(&self, newer: &SimpleBitmap) -> BitmapDelta {
+        BitmapDelta {
+            added: newer.andnot(self),
+            removed: self.andnot(newer),
+        }
+    }
+
+    /// Adds the added and removes the removed values of the delta.
+    pub fn apply(&mut self, delta: &BitmapDelta) {
+        *self |= &delta.added;
+        self.difference_with(&delta.removed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_replays_the_changes_between_two_bitmaps() {
+        // given
+        let older: SimpleBitmap = [1, 6, 40, 1_000].into_iter().collect();
+        let newer: SimpleBitmap = [6, 40, 70, 5_000].into_iter().collect();
+
+        // when
+        let delta = older.diff(&newer);
+        let mut replica = older.clone();
+        replica.apply(&delta);
+
+        // then
+        assert_eq!(delta.added.iter().collect::<Vec<u32>>(), vec![70, 5_000]);
+        assert_eq!(delta.removed.iter().collect::<Vec<u32>>(), vec![1, 1_000]);
+        assert_eq!(replica, newer);
+        assert!(newer.diff(&replica).is_empty());
+    }
+}
@@ -0,0 +1,532 @@
+use crate::SimpleBitmap;
+use core::iter::Enumerate;
+use core::ops::{Range, RangeInclusive};
+use core::slice;
+
+/// Iterator over the indices of the values present in a bitmap backed by u32s, in ascending order.
+///
+/// Created by [SimpleBitmap::iter](crate::SimpleBitmap::iter) and [FixedBitmap::iter](crate::FixedBitmap::iter).
+#[derive(Clone)]
+pub struct Ones<'a> {
+    buckets: Enumerate<slice::Iter<'a, u32>>,
+    front_bucket: u32,
+    front_bucket_base: u32,
+    back_bucket: u32,
+    back_bucket_base: u32,
+}
+
+impl<'a> Ones<'a> {
+    pub(crate) fn new(bits: &'a [u32]) -> Self {
+        Self {
+            buckets: bits.iter().enumerate(),
+            front_bucket: 0,
+            front_bucket_base: 0,
+            back_bucket: 0,
+            back_bucket_base: 0,
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip u32s without any bit set instead of probing every single index
+        while self.front_bucket == 0 {
+            if let Some((u32_index_in_bits_vec, bucket)) = self.buckets.next() {
+                self.front_bucket = *bucket;
+                self.front_bucket_base = u32_index_in_bits_vec as u32 * 32;
+            } else {
+                // the back end might already hold the last partially consumed u32
+                self.front_bucket = core::mem::take(&mut self.back_bucket);
+                self.front_bucket_base = self.back_bucket_base;
+
+                if self.front_bucket == 0 {
+                    return None;
+                }
+            }
+        }
+
+        let bit_index_in_u32 = self.front_bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.front_bucket &= self.front_bucket - 1;
+
+        Some(self.front_bucket_base + bit_index_in_u32)
+    }
+}
+
+impl DoubleEndedIterator for Ones<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // skip u32s without any bit set instead of probing every single index
+        while self.back_bucket == 0 {
+            if let Some((u32_index_in_bits_vec, bucket)) = self.buckets.next_back() {
+                self.back_bucket = *bucket;
+                self.back_bucket_base = u32_index_in_bits_vec as u32 * 32;
+            } else {
+                // the front end might already hold the last partially consumed u32
+                self.back_bucket = core::mem::take(&mut self.front_bucket);
+                self.back_bucket_base = self.front_bucket_base;
+
+                if self.back_bucket == 0 {
+                    return None;
+                }
+            }
+        }
+
+        let bit_index_in_u32 = 31 - self.back_bucket.leading_zeros();
+
+        // unset the highest set bit, so the next call yields the preceding one
+        self.back_bucket &= !(0b1 << bit_index_in_u32);
+
+        Some(self.back_bucket_base + bit_index_in_u32)
+    }
+}
+
+/// Iterator over the indices of the values absent from a [SimpleBitmap](crate::SimpleBitmap) below an upper bound, in
+/// ascending order.
+///
+/// Created by [SimpleBitmap::iter_zeros](crate::SimpleBitmap::iter_zeros).
+#[derive(Clone)]
+pub struct Zeros<'a> {
+    bits: &'a [u32],
+    upper_bound: u32,
+    next_u32_index_in_bits_vec: usize,
+    bucket: u32,
+    bucket_base: u32,
+}
+
+impl<'a> Zeros<'a> {
+    pub(crate) fn new(bits: &'a [u32], upper_bound: u32) -> Self {
+        Self {
+            bits,
+            upper_bound,
+            next_u32_index_in_bits_vec: 0,
+            bucket: 0,
+            bucket_base: 0,
+        }
+    }
+}
+
+impl Iterator for Zeros<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip u32s without any bit cleared instead of probing every single index
+        while self.bucket == 0 {
+            let bucket_base = (self.next_u32_index_in_bits_vec as u64) * 32;
+            if bucket_base >= u64::from(self.upper_bound) {
+                return None;
+            }
+
+            // u32s beyond the end of the bits vec have all bits cleared
+            let bucket = self
+                .bits
+                .get(self.next_u32_index_in_bits_vec)
+                .copied()
+                .unwrap_or(0);
+
+            self.bucket = !bucket;
+            self.bucket_base = bucket_base as u32;
+            self.next_u32_index_in_bits_vec += 1;
+
+            // bits at or above the upper bound must not be yielded
+            let bits_below_upper_bound = self.upper_bound - self.bucket_base;
+            if bits_below_upper_bound < 32 {
+                self.bucket &= !(u32::MAX << bits_below_upper_bound);
+            }
+        }
+
+        let bit_index_in_u32 = self.bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.bucket &= self.bucket - 1;
+
+        Some(self.bucket_base + bit_index_in_u32)
+    }
+}
+
+/// Iterator over the maximal runs of consecutive present values of a [SimpleBitmap](crate::SimpleBitmap), in ascending
+/// order.
+///
+/// As ranges are exclusive, a run that includes `u32::MAX` ends at `u32::MAX`, i.e. the last index is not part of it.
+/// [RunsInclusive] yields such runs completely.
+///
+/// Created by [SimpleBitmap::runs](crate::SimpleBitmap::runs).
+#[derive(Clone)]
+pub struct Runs<'a> {
+    runs: RunsInclusive<'a>,
+}
+
+impl<'a> Runs<'a> {
+    pub(crate) fn new(bits: &'a [u32]) -> Self {
+        Self {
+            runs: RunsInclusive::new(bits),
+        }
+    }
+}
+
+impl Iterator for Runs<'_> {
+    type Item = Range<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runs
+            .next()
+            .map(|run| *run.start()..run.end().saturating_add(1))
+    }
+}
+
+/// Iterator over the maximal runs of consecutive present values of a [SimpleBitmap](crate::SimpleBitmap) as inclusive
+/// ranges, in ascending order, so that runs including `u32::MAX` can be represented.
+///
+/// Created by [SimpleBitmap::runs_inclusive](crate::SimpleBitmap::runs_inclusive).
+#[derive(Clone)]
+pub struct RunsInclusive<'a> {
+    bits: &'a [u32],
+    next_index: u64,
+}
+
+impl<'a> RunsInclusive<'a> {
+    pub(crate) fn new(bits: &'a [u32]) -> Self {
+        Self {
+            bits,
+            next_index: 0,
+        }
+    }
+}
+
+impl Iterator for RunsInclusive<'_> {
+    type Item = RangeInclusive<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = next_index_where(self.bits, self.next_index, |bucket| bucket)?;
+
+        // u32s beyond the end of the bits vec have all bits cleared, so an end of the run always exists
+        let end = next_index_where(self.bits, start, |bucket| !bucket)
+            .unwrap_or(self.bits.len() as u64 * 32);
+
+        self.next_index = end;
+
+        Some(start as u32..=(end - 1) as u32)
+    }
+}
+
+/// Cursor over the indices of the present values of a [SimpleBitmap], in ascending order, that can skip ahead to any
+/// index. Advancing to the IDs of another sorted sequence intersects both without visiting every value.
+///
+/// Created by [SimpleBitmap::cursor](crate::SimpleBitmap::cursor).
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    bits: &'a [u32],
+    // no value below this index is yielded anymore
+    position: u64,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(bits: &'a [u32]) -> Self {
+        Self { bits, position: 0 }
+    }
+
+    /// Moves the cursor to the lowest present value at or above the given index and returns it, without consuming it.
+    /// The cursor never moves backwards, so advancing to an index below its position returns the current value.
+    pub fn advance_to(&mut self, index: u32) -> Option<u32> {
+        self.position = self.position.max(u64::from(index));
+        self.peek()
+    }
+
+    /// Returns the value the next call to [Iterator::next] yields, without consuming it.
+    pub fn peek(&mut self) -> Option<u32> {
+        // whole u32s without any bit set are skipped
+        let index = next_index_where(self.bits, self.position, |bucket| bucket)?;
+        self.position = index;
+
+        Some(index as u32)
+    }
+}
+
+impl Iterator for Cursor<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.peek()?;
+        self.position = u64::from(index) + 1;
+
+        Some(index)
+    }
+}
+
+/// Iterator over the indices of the values resulting from combining two [SimpleBitmap]s, in ascending order. The u32s
+/// are combined one at a time while iterating, so no bitmap is allocated for the result.
+///
+/// Created by [SimpleBitmap::iter_and](crate::SimpleBitmap::iter_and),
+/// [SimpleBitmap::iter_or](crate::SimpleBitmap::iter_or) and [SimpleBitmap::iter_diff](crate::SimpleBitmap::iter_diff).
+#[derive(Clone)]
+pub struct CombinedOnes<'a> {
+    left: &'a [u32],
+    right: &'a [u32],
+    op: fn(u32, u32) -> u32,
+    // u32s at and beyond this index don't contribute any value
+    len: usize,
+    u32_index_in_bits_vec: usize,
+    bucket: u32,
+}
+
+impl<'a> CombinedOnes<'a> {
+    pub(crate) fn new(
+        left: &'a [u32],
+        right: &'a [u32],
+        op: fn(u32, u32) -> u32,
+        len: usize,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            op,
+            len,
+            u32_index_in_bits_vec: 0,
+            bucket: 0,
+        }
+    }
+}
+
+impl Iterator for CombinedOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket == 0 {
+            if self.u32_index_in_bits_vec >= self.len {
+                return None;
+            }
+
+            // u32s beyond the end of either bits vec have all bits cleared
+            let left = self
+                .left
+                .get(self.u32_index_in_bits_vec)
+                .copied()
+                .unwrap_or(0);
+            let right = self
+                .right
+                .get(self.u32_index_in_bits_vec)
+                .copied()
+                .unwrap_or(0);
+
+            self.bucket = (self.op)(left, right);
+            self.u32_index_in_bits_vec += 1;
+        }
+
+        let bit_index_in_u32 = self.bucket.trailing_zeros();
+        self.bucket &= self.bucket - 1;
+
+        Some((self.u32_index_in_bits_vec as u32 - 1) * 32 + bit_index_in_u32)
+    }
+}
+
+/// Finds the first index at or after `from` whose bit is set in the u32s of the bits vec after applying `transform` to
+/// them, by scanning whole u32s.
+pub(super) fn next_index_where(
+    bits: &[u32],
+    from: u64,
+    transform: impl Fn(u32) -> u32,
+) -> Option<u64> {
+    let mut u32_index_in_bits_vec = (from / 32) as usize;
+
+    // bits below from are masked out in the first u32
+    let mut mask = u32::MAX << (from & 0b11111);
+
+    while let Some(bucket) = bits.get(u32_index_in_bits_vec) {
+        let candidates = transform(*bucket) & mask;
+
+        if candidates != 0 {
+            return Some(
+                u32_index_in_bits_vec as u64 * 32 + u64::from(candidates.trailing_zeros()),
+            );
+        }
+
+        u32_index_in_bits_vec += 1;
+        mask = u32::MAX;
+    }
+
+    None
+}
+
+impl<'a> IntoIterator for &'a SimpleBitmap {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bitmap, SimpleBitmap};
+    use core::ops::{Range, RangeInclusive};
+
+    #[test]
+    fn it_iterates_over_set_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(0);
+        bm.set(31);
+        bm.set(32);
+        bm.set(1000);
+
+        // when
+        let indices: Vec<u32> = bm.iter().collect();
+
+        // then
+        assert_eq!(indices, vec![0, 31, 32, 1000]);
+        assert_eq!(SimpleBitmap::new().iter().next(), None);
+    }
+
+    #[test]
+    fn it_iterates_over_set_bits_in_reverse() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(0);
+        bm.set(31);
+        bm.set(32);
+        bm.set(1000);
+
+        // when
+        let highest_two: Vec<u32> = bm.iter().rev().take(2).collect();
+
+        // then
+        assert_eq!(highest_two, vec![1000, 32]);
+        assert_eq!(bm.iter().rev().collect::<Vec<u32>>(), vec![1000, 32, 31, 0]);
+    }
+
+    #[test]
+    fn it_iterates_over_set_bits_from_both_ends() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(1);
+        bm.set(3);
+        bm.set(5);
+        bm.set(40);
+
+        // when
+        let mut ones = bm.iter();
+
+        // then
+        assert_eq!(ones.next_back(), Some(40));
+        assert_eq!(ones.next(), Some(1));
+        assert_eq!(ones.next_back(), Some(5));
+        assert_eq!(ones.next(), Some(3));
+        assert_eq!(ones.next(), None);
+        assert_eq!(ones.next_back(), None);
+    }
+
+    #[test]
+    fn it_iterates_over_references_in_for_loops() {
+        // given
+        let bm: SimpleBitmap = [3, 31, 64].into_iter().collect();
+        let mut indices = Vec::new();
+
+        // when
+        for index in &bm {
+            indices.push(index);
+        }
+
+        // then
+        assert_eq!(indices, vec![3, 31, 64]);
+        assert_eq!((&bm).into_iter().map(|index| index * 2).max(), Some(128));
+    }
+
+    #[test]
+    fn it_iterates_over_unset_bits_below_a_bound() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set_range(0..40);
+        bm.clear(3);
+        bm.clear(35);
+
+        // when
+        let free_slots: Vec<u32> = bm.iter_zeros(45).collect();
+
+        // then
+        assert_eq!(free_slots, vec![3, 35, 40, 41, 42, 43, 44]);
+        assert_eq!(bm.iter_zeros(36).collect::<Vec<u32>>(), vec![3, 35]);
+        assert_eq!(bm.iter_zeros(0).next(), None);
+        assert_eq!(SimpleBitmap::new().iter_zeros(64).count(), 64);
+    }
+
+    #[test]
+    fn it_iterates_over_runs_of_set_bits() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(1);
+        bm.set_range(6..9);
+        bm.set_range(30..100);
+        bm.set(127);
+
+        // when
+        let runs: Vec<Range<u32>> = bm.runs().collect();
+
+        // then
+        assert_eq!(runs, vec![1..2, 6..9, 30..100, 127..128]);
+        assert_eq!(SimpleBitmap::new().runs().next(), None);
+    }
+
+    #[test]
+    fn it_iterates_over_runs_ending_at_the_highest_index() {
+        // given
+        let mut bm = SimpleBitmap::new();
+        bm.set(3);
+        bm.set_range(u32::MAX - 40..u32::MAX);
+        bm.set(u32::MAX);
+
+        // then
+        assert_eq!(
+            bm.runs_inclusive().collect::<Vec<RangeInclusive<u32>>>(),
+            vec![3..=3, u32::MAX - 40..=u32::MAX]
+        );
+        assert_eq!(
+            bm.runs().collect::<Vec<Range<u32>>>(),
+            vec![3..4, u32::MAX - 40..u32::MAX]
+        );
+        assert_eq!(bm.to_string(), "3, 4294967255-4294967295");
+        assert_eq!(bm.stats().longest_run, 41);
+    }
+
+    #[test]
+    fn it_advances_the_cursor_to_the_next_present_value() {
+        // given
+        let bm: SimpleBitmap = [3, 40, 41, 1_000, 5_000].into_iter().collect();
+        let ids = [2, 40, 41, 900, 1_000, 6_000];
+
+        // when
+        let mut cursor = bm.cursor();
+        let common: Vec<u32> = ids
+            .into_iter()
+            .filter(|id| cursor.advance_to(*id) == Some(*id))
+            .collect();
+
+        // then
+        assert_eq!(common, vec![40, 41, 1_000]);
+
+        let mut cursor = bm.cursor();
+        assert_eq!(cursor.advance_to(4), Some(40));
+        assert_eq!(cursor.advance_to(0), Some(40));
+        assert_eq!(cursor.next(), Some(40));
+        assert_eq!(cursor.next(), Some(41));
+        assert_eq!(cursor.advance_to(5_001), None);
+    }
+
+    #[test]
+    fn it_combines_two_bitmaps_while_iterating() {
+        // given
+        let left: SimpleBitmap = [1, 31, 40, 1_000].into_iter().collect();
+        let right: SimpleBitmap = [31, 40, 70].into_iter().collect();
+
+        // then
+        assert_eq!(left.iter_and(&right).collect::<Vec<u32>>(), vec![31, 40]);
+        assert_eq!(
+            left.iter_or(&right).collect::<Vec<u32>>(),
+            vec![1, 31, 40, 70, 1_000]
+        );
+        assert_eq!(left.iter_diff(&right).collect::<Vec<u32>>(), vec![1, 1_000]);
+        assert_eq!(right.iter_diff(&left).collect::<Vec<u32>>(), vec![70]);
+    }
+}
@@ -0,0 +1,149 @@
+//! Bulk operations over the u32s of a bitmap.
+//!
+//! With the `simd` feature, the u32s are processed in lanes of [LANES] via `core::simd`, which requires a nightly
+//! compiler. Otherwise plain scalar loops are used, which the compiler may still auto-vectorize.
+
+#[cfg(feature = "simd")]
+use core::simd::{Simd, num::SimdUint};
+
+#[cfg(feature = "simd")]
+const LANES: usize = 8;
+
+/// Sets all bits in `left` that are set in `right`. Both slices must have the same length.
+pub(crate) fn or_into(left: &mut [u32], right: &[u32]) {
+    zip_with(left, right, |left, right| left | right);
+
+    #[cfg(feature = "simd")]
+    simd_zip_with(left, right, |left, right| left | right);
+}
+
+/// Clears all bits in `left` that are not set in `right`. Both slices must have the same length.
+pub(crate) fn and_into(left: &mut [u32], right: &[u32]) {
+    zip_with(left, right, |left, right| left & right);
+
+    #[cfg(feature = "simd")]
+    simd_zip_with(left, right, |left, right| left & right);
+}
+
+/// Flips all bits in `left` that are set in `right`. Both slices must have the same length.
+pub(crate) fn xor_into(left: &mut [u32], right: &[u32]) {
+    zip_with(left, right, |left, right| left ^ right);
+
+    #[cfg(feature = "simd")]
+    simd_zip_with(left, right, |left, right| left ^ right);
+}
+
+/// Clears all bits in `left` that are set in `right`. Both slices must have the same length.
+pub(crate) fn and_not_into(left: &mut [u32], right: &[u32]) {
+    zip_with(left, right, |left, right| left & !right);
+
+    #[cfg(feature = "simd")]
+    simd_zip_with(left, right, |left, right| left & !right);
+}
+
+/// Returns the number of set bits in all u32s.
+#[cfg(not(feature = "simd"))]
+pub(crate) fn count_ones(bits: &[u32]) -> u64 {
+    bits.iter()
+        .map(|bucket| u64::from(bucket.count_ones()))
+        .sum()
+}
+
+/// Returns the number of set bits in all u32s.
+#[cfg(feature = "simd")]
+pub(crate) fn count_ones(bits: &[u32]) -> u64 {
+    let (chunks, tail) = bits.as_chunks::<LANES>();
+
+    // the per-lane counts are summed up as u64s, so that even the largest bitmap cannot overflow them
+    let lanes: u64 = chunks
+        .iter()
+        .map(|chunk| {
+            Simd::from_array(*chunk)
+                .count_ones()
+                .cast::<u64>()
+                .reduce_sum()
+        })
+        .sum();
+
+    lanes
+        + tail
+            .iter()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum::<u64>()
+}
+
+/// Combines the u32s of both slices pairwise with `op`, storing the result in `left`.
+///
+/// With the `simd` feature, only the tail that does not fill a whole lane is handled here.
+fn zip_with(left: &mut [u32], right: &[u32], op: impl Fn(u32, u32) -> u32) {
+    debug_assert_eq!(left.len(), right.len());
+
+    #[cfg(feature = "simd")]
+    let (left, right) = {
+        let start = left.len() - left.len() % LANES;
+        (&mut left[start..], &right[start..])
+    };
+
+    for (left, right) in left.iter_mut().zip(right) {
+        *left = op(*left, *right);
+    }
+}
+
+/// Combines the u32s of both slices lane-wise with `op`, storing the result in `left`. The tail that does not fill a
+/// whole lane is left untouched.
+#[cfg(feature = "simd")]
+fn simd_zip_with(
+    left: &mut [u32],
+    right: &[u32],
+    op: impl Fn(Simd<u32, LANES>, Simd<u32, LANES>) -> Simd<u32, LANES>,
+) {
+    let (left_chunks, _) = left.as_chunks_mut::<LANES>();
+    let (right_chunks, _) = right.as_chunks::<LANES>();
+
+    for (left, right) in left_chunks.iter_mut().zip(right_chunks) {
+        *left = op(Simd::from_array(*left), Simd::from_array(*right)).to_array();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_combines_slices_of_any_length() {
+        // lengths below, at and above the lane width exercise both the lanes and the tail
+        for len in [0, 1, 7, 8, 9, 33] {
+            // given
+            let left: Vec<u32> = (0..len as u32)
+                .map(|i| i.wrapping_mul(0x9e3779b9))
+                .collect();
+            let right: Vec<u32> = (0..len as u32)
+                .map(|i| i.wrapping_mul(0x85ebca6b))
+                .collect();
+
+            // when
+            let mut union = left.clone();
+            or_into(&mut union, &right);
+            let mut intersection = left.clone();
+            and_into(&mut intersection, &right);
+            let mut symmetric_difference = left.clone();
+            xor_into(&mut symmetric_difference, &right);
+            let mut difference = left.clone();
+            and_not_into(&mut difference, &right);
+
+            // then
+            for i in 0..len {
+                assert_eq!(union[i], left[i] | right[i]);
+                assert_eq!(intersection[i], left[i] & right[i]);
+                assert_eq!(symmetric_difference[i], left[i] ^ right[i]);
+                assert_eq!(difference[i], left[i] & !right[i]);
+            }
+            assert_eq!(
+                count_ones(&left),
+                left.iter()
+                    .map(|bucket| u64::from(bucket.count_ones()))
+                    .sum()
+            );
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::{SimpleBitmap, kernels};
+use alloc::vec;
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// Number of u32s processed by a single task, large enough to outweigh the cost of scheduling it.
+const CHUNK_LEN: usize = 4096;
+
+impl SimpleBitmap {
+    /// Counts the values that are present, spreading the u32s over the threads of the rayon pool.
+    pub fn par_count_ones(&self) -> u64 {
+        self.bits
+            .par_chunks(CHUNK_LEN)
+            .map(kernels::count_ones)
+            .sum()
+    }
+
+    /// Adds all values of `other` to this bitmap, spreading the u32s over the threads of the rayon pool.
+    pub fn par_union_with(&mut self, other: &SimpleBitmap) {
+        if other.bits.len() > self.bits.len() {
+            self.bits.resize(other.bits.len(), 0);
+        }
+
+        self.bits[..other.bits.len()]
+            .par_chunks_mut(CHUNK_LEN)
+            .zip(other.bits.par_chunks(CHUNK_LEN))
+            .for_each(|(left, right)| kernels::or_into(left, right));
+    }
+
+    /// Removes all values from this bitmap that are absent in `other`, spreading the u32s over the threads of the rayon
+    /// pool.
+    pub fn par_intersect_with(&mut self, other: &SimpleBitmap) {
+        // u32s beyond the end of other are and-ed with implicit zeros
+        self.bits.truncate(other.bits.len());
+
+        self.bits
+            .par_chunks_mut(CHUNK_LEN)
+            .zip(other.bits.par_chunks(CHUNK_LEN))
+            .for_each(|(left, right)| kernels::and_into(left, right));
+    }
+
+    /// Returns the union of all given bitmaps like [SimpleBitmap::union_all], with every thread of the rayon pool
+    /// merging all bitmaps into a different range of u32s of the result.
+    pub fn par_union_all<'a>(bitmaps: impl IntoIterator<Item = &'a SimpleBitmap>) -> SimpleBitmap {
+        let words: Vec<&[u32]> = bitmaps
+            .into_iter()
+            .map(|bitmap| bitmap.trimmed_bits())
+            .collect();
+
+        let len = words.iter().map(|bits| bits.len()).max().unwrap_or(0);
+        let mut union = vec![0; len];
+
+        union
+            .par_chunks_mut(CHUNK_LEN)
+            .enumerate()
+            .for_each(|(chunk_index, chunk)| {
+                let start = chunk_index * CHUNK_LEN;
+
+                // bitmaps ending before this chunk have nothing to contribute
+                for bits in words.iter().filter(|bits| bits.len() > start) {
+                    let end = bits.len().min(start + chunk.len());
+                    kernels::or_into(&mut chunk[..end - start], &bits[start..end]);
+                }
+            });
+
+        SimpleBitmap { bits: union }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+
+    #[test]
+    fn it_combines_bitmaps_like_the_sequential_operations() {
+        // given
+        let left: SimpleBitmap = (0..1_000_000).step_by(3).collect();
+        let right: SimpleBitmap = (0..300_000).step_by(5).collect();
+
+        // when
+        let mut union = right.clone();
+        union.par_union_with(&left);
+        let mut intersection = left.clone();
+        intersection.par_intersect_with(&right);
+
+        // then
+        assert_eq!(union, left.clone() | right.clone());
+        assert_eq!(intersection, left.clone() & right.clone());
+        assert_eq!(union.par_count_ones(), union.count_ones());
+    }
+
+    #[test]
+    fn it_unions_all_bitmaps_in_parallel() {
+        // given
+        let bitmaps: Vec<SimpleBitmap> = (0..1_000)
+            .map(|offset| (offset * 300..offset * 300 + 600).step_by(7).collect())
+            .collect();
+
+        // when
+        let union = SimpleBitmap::par_union_all(&bitmaps);
+
+        // then
+        assert_eq!(union, SimpleBitmap::union_all(&bitmaps));
+        assert_eq!(SimpleBitmap::par_union_all([]), SimpleBitmap::new());
+    }
+}
@@ -0,0 +1,72 @@
+use super::{SimpleBitmap, select_in_u32};
+use crate::Bitmap;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use rand::{Rng, RngExt};
+
+impl SimpleBitmap {
+    /// Picks `k` distinct present values uniformly at random and returns them in ascending order. If fewer than `k`
+    /// values are present, all of them are returned.
+    pub fn sample<R: Rng + ?Sized>(&self, k: usize, rng: &mut R) -> Vec<u32> {
+        let count = self.count_ones();
+        if k as u64 >= count {
+            return self.iter().collect();
+        }
+
+        // the values are picked by their rank, which is a u64 as there can be more values than fit in a usize, with
+        // Floyd's algorithm, drawing exactly one random number per picked rank
+        let mut ranks = BTreeSet::new();
+        for upper_rank in count - k as u64..count {
+            let rank = rng.random_range(0..=upper_rank);
+            if !ranks.insert(rank) {
+                ranks.insert(upper_rank);
+            }
+        }
+
+        // the ranks are sorted, so a single pass over the u32s finds all of them
+        let mut ranks = ranks.into_iter().peekable();
+        let mut ones_before = 0;
+        let mut sample = Vec::with_capacity(k);
+
+        for (u32_index_in_bits_vec, bucket) in self.bits.iter().enumerate() {
+            if ranks.peek().is_none() {
+                break;
+            }
+
+            let ones = u64::from(bucket.count_ones());
+
+            while let Some(rank) = ranks.next_if(|rank| *rank < ones_before + ones) {
+                let bit_index_in_u32 = select_in_u32(*bucket, (rank - ones_before) as u32);
+                sample.push(u32_index_in_bits_vec as u32 * 32 + bit_index_in_u32);
+            }
+
+            ones_before += ones;
+        }
+
+        sample
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    #[test]
+    fn it_samples_distinct_present_values() {
+        // given
+        let bm: SimpleBitmap = (0..10_000).step_by(7).collect();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        // when
+        let sample = bm.sample(100, &mut rng);
+
+        // then
+        assert_eq!(sample.len(), 100);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sample.iter().all(|value| bm.get(*value)));
+        assert_eq!(bm.sample(5_000, &mut rng), bm.iter().collect::<Vec<u32>>());
+        assert_eq!(SimpleBitmap::new().sample(3, &mut rng), Vec::<u32>::new());
+    }
+}
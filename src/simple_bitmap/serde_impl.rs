@@ -0,0 +1,121 @@
+use super::{MAX_LEN, SimpleBitmap};
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for SimpleBitmap {
+    /// Human-readable formats like JSON get the sorted indices of the present values, compact formats get the u32s.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter())
+        } else {
+            // trailing zero u32s are skipped, so that equal bitmaps always produce equal output
+            self.trimmed_bits().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SimpleBitmap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let values = Vec::<u32>::deserialize(deserializer)?;
+
+            Ok(values.into_iter().collect())
+        } else {
+            deserializer.deserialize_seq(WordsVisitor)
+        }
+    }
+}
+
+/// Collects the u32s of the compact format, rejecting more u32s than are needed to hold every index up to u32::MAX.
+struct WordsVisitor;
+
+impl<'de> Visitor<'de> for WordsVisitor {
+    type Value = SimpleBitmap;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(formatter, "a sequence of at most {MAX_LEN} u32s")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // sequences announcing too many u32s are rejected before any of them is read
+        if let Some(len) = seq.size_hint()
+            && len > MAX_LEN
+        {
+            return Err(de::Error::invalid_length(len, &self));
+        }
+
+        let mut words = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(word) = seq.next_element()? {
+            if words.len() == MAX_LEN {
+                return Err(de::Error::invalid_length(MAX_LEN + 1, &self));
+            }
+            words.push(word);
+        }
+
+        Ok(SimpleBitmap::from_words(words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bitmap;
+    use core::iter;
+    use serde::de::value::SeqDeserializer;
+    use serde_test::{Configure, Token, assert_tokens};
+
+    #[test]
+    fn it_serializes_indices_for_human_readable_formats() {
+        // given
+        let bm: SimpleBitmap = [1, 40].into_iter().collect();
+
+        // then
+        assert_tokens(
+            &bm.readable(),
+            &[
+                Token::Seq { len: None },
+                Token::U32(1),
+                Token::U32(40),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn it_serializes_u32s_for_compact_formats() {
+        // given
+        let mut bm: SimpleBitmap = [1, 40, 100].into_iter().collect();
+        bm.clear(100);
+
+        // then
+        assert_tokens(
+            &bm.compact(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U32(0b10),
+                Token::U32(0b1 << 8),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn it_rejects_more_u32s_than_the_index_space_of_a_u32_needs() {
+        // given
+        let too_long =
+            SeqDeserializer::<_, de::value::Error>::new(iter::repeat_n(0u32, MAX_LEN + 1));
+
+        // when
+        let result = SimpleBitmap::deserialize(too_long.compact());
+
+        // then
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .starts_with("invalid length 134217729")
+        );
+    }
+}
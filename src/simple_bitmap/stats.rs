@@ -0,0 +1,141 @@
+use super::SimpleBitmap;
+use crate::Bitmap;
+use alloc::vec::Vec;
+
+/// Number of u32s in a block of 65536 values, the range of values covered by a container of a
+/// [RoaringBitmap](crate::RoaringBitmap).
+const BLOCK_WORDS: usize = 2048;
+
+/// Estimated bytes per non-zero u32 in a [SparseBitmap](crate::SparseBitmap), including the overhead of the tree.
+const SPARSE_BYTES_PER_WORD: usize = 16;
+
+/// Statistics about the distribution of the values in a [SimpleBitmap].
+///
+/// Created by [SimpleBitmap::stats].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitmapStats {
+    /// Number of present values.
+    pub count: u64,
+    /// Number of runs of consecutive present values.
+    pub runs: u64,
+    /// Number of values in the longest run.
+    pub longest_run: u64,
+    /// Share of present values in each block of 65536 values, up to the block of the highest present value.
+    pub block_densities: Vec<f64>,
+}
+
+/// Implementations of a bitmap that [SimpleBitmap::advise] can recommend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Representation {
+    Simple,
+    Sparse,
+    Roaring,
+}
+
+impl SimpleBitmap {
+    /// Collects statistics about the distribution of the present values.
+    pub fn stats(&self) -> BitmapStats {
+        let mut runs = 0;
+        let mut longest_run = 0;
+        for run in self.runs_inclusive() {
+            runs += 1;
+            longest_run = longest_run.max(u64::from(run.end() - run.start()) + 1);
+        }
+
+        BitmapStats {
+            count: self.count_ones(),
+            runs,
+            longest_run,
+            block_densities: self
+                .trimmed_bits()
+                .chunks(BLOCK_WORDS)
+                .map(|block| super::kernels::count_ones(block) as f64 / (BLOCK_WORDS as f64 * 32.0))
+                .collect(),
+        }
+    }
+
+    /// Recommends the implementation that would store the present values in the least memory.
+    ///
+    /// The sizes are estimated from the number of u32s, values and runs of every block, so the recommendation is a
+    /// heuristic rather than an exact comparison.
+    pub fn advise(&self) -> Representation {
+        let bits = self.trimmed_bits();
+
+        let simple = 4 * bits.len();
+        let sparse = SPARSE_BYTES_PER_WORD * bits.iter().filter(|bucket| **bucket != 0).count();
+
+        // every non-empty block becomes a container, which stores its values as array, bitset or runs
+        let roaring: usize = bits
+            .chunks(BLOCK_WORDS)
+            .map(|block| {
+                let count = super::kernels::count_ones(block) as usize;
+                if count == 0 {
+                    return 0;
+                }
+
+                let runs = run_starts(block);
+                4 + (2 * count).min(8192).min(4 * runs)
+            })
+            .sum();
+
+        if simple <= sparse && simple <= roaring {
+            Representation::Simple
+        } else if sparse < roaring {
+            Representation::Sparse
+        } else {
+            Representation::Roaring
+        }
+    }
+}
+
+/// Counts the runs starting in the u32s, i.e. the set bits whose preceding bit is unset.
+fn run_starts(bits: &[u32]) -> usize {
+    let mut previous_highest_bit = 0;
+
+    bits.iter()
+        .map(|bucket| {
+            let preceding = bucket << 1 | previous_highest_bit;
+            previous_highest_bit = bucket >> 31;
+
+            (bucket & !preceding).count_ones() as usize
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_collects_statistics_about_runs_and_blocks() {
+        // given
+        let mut bm: SimpleBitmap = [1, 5, 100_000].into_iter().collect();
+        bm.set_range(10..50);
+
+        // when
+        let stats = bm.stats();
+
+        // then
+        assert_eq!(stats.count, 43);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.longest_run, 40);
+        assert_eq!(stats.block_densities, vec![42.0 / 65536.0, 1.0 / 65536.0]);
+    }
+
+    #[test]
+    fn it_advises_the_smallest_representation() {
+        // given
+        let dense: SimpleBitmap = (0..100_000).step_by(2).collect();
+        let alternating_words: SimpleBitmap = (0..64)
+            .flat_map(|block| (0..32).step_by(2).map(move |bit| block << 16 | bit))
+            .collect();
+        let mut long_run = SimpleBitmap::new();
+        long_run.set_range(0..1_000_000);
+
+        // then
+        assert_eq!(dense.advise(), Representation::Simple);
+        assert_eq!(alternating_words.advise(), Representation::Sparse);
+        assert_eq!(long_run.advise(), Representation::Roaring);
+        assert_eq!(SimpleBitmap::new().advise(), Representation::Simple);
+    }
+}
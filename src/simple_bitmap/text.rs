@@ -0,0 +1,165 @@
+use super::{SimpleBitmap, check_payload_len};
+use crate::error::DecodeError;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// URL- and filename-safe base64 alphabet of RFC 4648.
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl SimpleBitmap {
+    /// Encodes the bitmap as lowercase hex digits, two per byte of its u32s in little-endian byte order.
+    ///
+    /// Trailing zero bytes are omitted, so the bitmap holding only the value 0 is encoded as `01`, and the empty bitmap
+    /// as the empty string.
+    pub fn to_hex(&self) -> String {
+        self.to_le_bytes()
+            .into_iter()
+            .flat_map(|byte| {
+                [
+                    HEX_DIGITS[usize::from(byte >> 4)],
+                    HEX_DIGITS[usize::from(byte & 0xf)],
+                ]
+            })
+            .map(char::from)
+            .collect()
+    }
+
+    /// Decodes a bitmap from the hex digits written by [SimpleBitmap::to_hex]. Upper case digits are accepted as well.
+    pub fn from_hex(hex: &str) -> Result<Self, DecodeError> {
+        if !hex.len().is_multiple_of(2) {
+            return Err(DecodeError::Invalid("odd number of hex digits"));
+        }
+        check_payload_len(hex.len() as u64 / 2)?;
+
+        let bytes = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| Ok(hex_value(pair[0])? << 4 | hex_value(pair[1])?))
+            .collect::<Result<Vec<u8>, DecodeError>>()?;
+
+        Ok(SimpleBitmap::from_le_bytes(&bytes))
+    }
+
+    /// Encodes the bitmap in unpadded base64 with the URL-safe alphabet, so it can be embedded into JSON, config files
+    /// and URLs alike. The bytes are the same as for [SimpleBitmap::to_hex].
+    pub fn to_base64(&self) -> String {
+        let bytes = self.to_le_bytes();
+        let mut base64 = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+        for chunk in bytes.chunks(3) {
+            // three bytes are split into four 6 bit digits, a partial chunk only needs as many digits as it has bits
+            let group = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |group, (position, byte)| {
+                    group | u32::from(*byte) << (16 - 8 * position)
+                });
+
+            for digit in 0..=chunk.len() {
+                let value = (group >> (18 - 6 * digit)) & 0b111111;
+                base64.push(char::from(BASE64_DIGITS[value as usize]));
+            }
+        }
+
+        base64
+    }
+
+    /// Decodes a bitmap from the base64 written by [SimpleBitmap::to_base64]. Trailing `=` padding is accepted.
+    pub fn from_base64(base64: &str) -> Result<Self, DecodeError> {
+        let digits = base64.trim_end_matches('=').as_bytes();
+
+        if digits.len() % 4 == 1 {
+            return Err(DecodeError::Invalid("incomplete base64 digit group"));
+        }
+        // every complete group of four digits holds three bytes, a partial group one byte less than it has digits
+        check_payload_len((digits.len() / 4 * 3 + (digits.len() % 4).saturating_sub(1)) as u64)?;
+
+        let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+
+        for chunk in digits.chunks(4) {
+            let mut group = 0u32;
+            for (position, digit) in chunk.iter().enumerate() {
+                group |= base64_value(*digit)? << (18 - 6 * position);
+            }
+
+            // four digits hold three bytes, a partial chunk one byte less than it has digits
+            for position in 0..chunk.len() - 1 {
+                bytes.push((group >> (16 - 8 * position)) as u8);
+            }
+        }
+
+        Ok(SimpleBitmap::from_le_bytes(&bytes))
+    }
+}
+
+fn hex_value(digit: u8) -> Result<u8, DecodeError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(DecodeError::Invalid("invalid hex digit")),
+    }
+}
+
+fn base64_value(digit: u8) -> Result<u32, DecodeError> {
+    BASE64_DIGITS
+        .iter()
+        .position(|candidate| *candidate == digit)
+        .map(|value| value as u32)
+        .ok_or(DecodeError::Invalid("invalid base64 digit"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_bitmaps_as_hex() {
+        // given
+        let bm: SimpleBitmap = [0, 4, 15, 32].into_iter().collect();
+
+        // when
+        let hex = bm.to_hex();
+
+        // then
+        assert_eq!(hex, "1180000001");
+        assert_eq!(SimpleBitmap::from_hex(&hex), Ok(bm));
+        assert_eq!(
+            SimpleBitmap::from_hex("118000000100"),
+            SimpleBitmap::from_hex(&hex)
+        );
+        assert_eq!(SimpleBitmap::new().to_hex(), "");
+        assert_eq!(SimpleBitmap::from_hex("FF"), Ok((0..8).collect()));
+        assert!(SimpleBitmap::from_hex("1").is_err());
+        assert!(SimpleBitmap::from_hex("1g").is_err());
+    }
+
+    #[test]
+    fn it_encodes_bitmaps_as_base64() {
+        // given
+        let bitmaps: Vec<SimpleBitmap> = vec![
+            SimpleBitmap::new(),
+            [0].into_iter().collect(),
+            [0, 9].into_iter().collect(),
+            [0, 9, 23].into_iter().collect(),
+            (0..1_000).step_by(7).collect(),
+        ];
+
+        for bm in bitmaps {
+            // when
+            let base64 = bm.to_base64();
+
+            // then
+            assert_eq!(SimpleBitmap::from_base64(&base64), Ok(bm));
+        }
+        // 0xfb 0xff encodes to digits from the URL-safe end of the alphabet
+        let bm: SimpleBitmap = (0..16).filter(|index| *index != 2).collect();
+        assert_eq!(bm.to_base64(), "-_8");
+        assert_eq!(SimpleBitmap::from_base64("-_8="), Ok(bm));
+        assert!(SimpleBitmap::from_base64("A").is_err());
+        assert!(SimpleBitmap::from_base64("A+").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use super::{BinaryWords, Ones, SimpleBitmap, count_ones_in};
+use core::fmt::{Debug, Formatter};
+use core::ops::Range;
+
+impl SimpleBitmap {
+    /// Returns a read-only window onto the values in the given range, in which the start of the range is the index 0.
+    pub fn view(&self, range: Range<u32>) -> BitmapView<'_> {
+        BitmapView {
+            bits: &self.bits,
+            start: range.start,
+            end: range.end.max(range.start),
+        }
+    }
+}
+
+/// Read-only window onto a range of a [SimpleBitmap] that indexes its values from the start of the range, without
+/// copying them.
+///
+/// Created by [SimpleBitmap::view].
+#[derive(Clone, Copy)]
+pub struct BitmapView<'a> {
+    bits: &'a [u32],
+    start: u32,
+    end: u32,
+}
+
+impl<'a> BitmapView<'a> {
+    /// Returns the number of indices covered by the window.
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the window doesn't cover any index.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns whether a value is present at the index relative to the start of the window.
+    pub fn get(&self, index: u32) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+
+        let index = self.start + index;
+        self.bits
+            .get((index / 32) as usize)
+            .is_some_and(|bucket| (bucket >> (index & 0b11111)) & 0b1 == 1)
+    }
+
+    /// Counts the values present within the window.
+    pub fn count_ones(&self) -> u64 {
+        count_ones_in(self.bits, self.start..self.end)
+    }
+
+    /// Returns an iterator over the indices of the present values relative to the start of the window, in ascending
+    /// order.
+    pub fn iter(&self) -> ViewOnes<'a> {
+        let first_u32_index = ((self.start / 32) as usize).min(self.bits.len());
+        let end_u32_index = (self.end.div_ceil(32) as usize).min(self.bits.len());
+        let base = first_u32_index as u32 * 32;
+
+        ViewOnes {
+            ones: Ones::new(&self.bits[first_u32_index..end_u32_index]),
+            start: self.start - base,
+            end: self.end - base,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &BitmapView<'a> {
+    type Item = u32;
+    type IntoIter = ViewOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the indices of the values present in a [BitmapView], in ascending order.
+///
+/// Created by [BitmapView::iter].
+#[derive(Clone)]
+pub struct ViewOnes<'a> {
+    // indices yielded by the iterator over the u32s are relative to the first u32 of the window
+    ones: Ones<'a>,
+    start: u32,
+    end: u32,
+}
+
+impl Iterator for ViewOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.ones.next()?;
+
+            if index >= self.end {
+                return None;
+            }
+            if index >= self.start {
+                return Some(index - self.start);
+            }
+        }
+    }
+}
+
+impl Debug for BitmapView<'_> {
+    /// Shows the range and a summary of the window. The alternate form `{:#?}` additionally dumps the u32s it covers in
+    /// binary.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let first_u32_index = ((self.start / 32) as usize).min(self.bits.len());
+        let end_u32_index =
+            (self.end.div_ceil(32) as usize).clamp(first_u32_index, self.bits.len());
+        let words = &self.bits[first_u32_index..end_u32_index];
+
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("BitmapView");
+        debug
+            .field("range", &(self.start..self.end))
+            .field("count", &self.count_ones())
+            .field("words", &words.len());
+
+        if alternate {
+            debug.field("bits", &BinaryWords(words));
+        }
+
+        debug.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_rebases_the_values_of_the_window() {
+        // given
+        let bm: SimpleBitmap = [3, 40, 41, 70, 100, 200].into_iter().collect();
+
+        // when
+        let view = bm.view(40..101);
+
+        // then
+        assert_eq!(view.len(), 61);
+        assert!(view.get(0));
+        assert!(view.get(60));
+        assert!(!view.get(61));
+        assert_eq!(view.count_ones(), 4);
+        assert_eq!(view.iter().collect::<Vec<u32>>(), vec![0, 1, 30, 60]);
+        assert_eq!(bm.view(300..400).count_ones(), 0);
+        assert_eq!(bm.view(300..400).iter().next(), None);
+        assert!(bm.view(5..5).is_empty());
+    }
+
+    #[test]
+    fn it_summarizes_the_window_in_debug_output() {
+        // given
+        let bm: SimpleBitmap = [1, 40, 70, 99, 201].into_iter().collect();
+
+        // when
+        let view = bm.view(40..100);
+
+        // then
+        assert_eq!(
+            format!("{view:?}"),
+            "BitmapView { range: 40..100, count: 3, words: 3 }"
+        );
+        assert!(format!("{view:#?}").contains(&format!("{:032b}", bm.words()[1])));
+        assert!(!format!("{view:#?}").contains(&format!("{:032b}", bm.words()[6])));
+        assert!(!format!("{view:?}").contains("bits"));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{Bitmap, SimpleBitmap};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Sub, SubAssign};
+use core::{iter, slice};
+
+/// Non-optimized implementation of a [Bitmap] like [SimpleBitmap], but backed by u64s.
+///
+/// On 64-bit targets, bulk operations need half as many iterations as with u32s.
+#[derive(Clone)]
+pub struct SimpleBitmap64 {
+    bits: Vec<u64>,
+}
+
+impl SimpleBitmap64 {
+    pub fn new() -> Self {
+        Self { bits: Vec::new() }
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|bucket| *bucket == 0)
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones64<'_> {
+        Ones64 {
+            buckets: self.bits.iter().enumerate(),
+            bucket: 0,
+            bucket_base: 0,
+        }
+    }
+
+    /// Returns the bits vec without trailing zero u64s, which do not carry any information.
+    fn trimmed_bits(&self) -> &[u64] {
+        let len = self
+            .bits
+            .iter()
+            .rposition(|bucket| *bucket != 0)
+            .map_or(0, |last_non_zero| last_non_zero + 1);
+
+        &self.bits[..len]
+    }
+}
+
+impl Bitmap for SimpleBitmap64 {
+    fn set(&mut self, index: u32) {
+        let u64_index_in_bits_vec = (index / 64) as usize;
+        let bit_index_in_u64 = index & 0b111111;
+
+        // if there is too little u64s in the bits vec, it has to be extended
+        if u64_index_in_bits_vec >= self.bits.len() {
+            self.bits.resize(u64_index_in_bits_vec + 1, 0);
+        }
+
+        self.bits[u64_index_in_bits_vec] |= 0b1 << bit_index_in_u64;
+    }
+
+    fn clear(&mut self, index: u32) {
+        if let Some(bucket) = self.bits.get_mut((index / 64) as usize) {
+            *bucket &= !(0b1 << (index & 0b111111));
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bits
+            .get((index / 64) as usize)
+            .is_some_and(|bucket| (bucket >> (index & 0b111111)) & 0b1 == 1)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bits
+            .iter()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum()
+    }
+}
+
+impl BitOrAssign<&SimpleBitmap64> for SimpleBitmap64 {
+    fn bitor_assign(&mut self, rhs: &SimpleBitmap64) {
+        // if there is too little u64s in the bits vec, it has to be extended so that it can hold all bits of rhs
+        if rhs.bits.len() > self.bits.len() {
+            self.bits.resize(rhs.bits.len(), 0);
+        }
+
+        for (left, right) in iter::zip(&mut self.bits, &rhs.bits) {
+            *left |= right;
+        }
+    }
+}
+
+impl BitAndAssign<&SimpleBitmap64> for SimpleBitmap64 {
+    fn bitand_assign(&mut self, rhs: &SimpleBitmap64) {
+        // u64s beyond the end of rhs can never be set in both bitmaps, so they are dropped
+        self.bits.truncate(rhs.bits.len());
+
+        for (left, right) in iter::zip(&mut self.bits, &rhs.bits) {
+            *left &= right;
+        }
+    }
+}
+
+impl SubAssign<&SimpleBitmap64> for SimpleBitmap64 {
+    fn sub_assign(&mut self, rhs: &SimpleBitmap64) {
+        // u64s beyond the end of rhs have nothing to clear
+        for (left, right) in iter::zip(&mut self.bits, &rhs.bits) {
+            *left &= !right;
+        }
+    }
+}
+
+impl BitOr for SimpleBitmap64 {
+    type Output = SimpleBitmap64;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self |= &rhs;
+        self
+    }
+}
+
+impl BitAnd for SimpleBitmap64 {
+    type Output = SimpleBitmap64;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self &= &rhs;
+        self
+    }
+}
+
+impl BitXor for SimpleBitmap64 {
+    type Output = SimpleBitmap64;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let (mut symmetric_difference, shorter) = if self.bits.len() >= rhs.bits.len() {
+            (self.bits, rhs.bits)
+        } else {
+            (rhs.bits, self.bits)
+        };
+
+        // u64s of the longer vec beyond the end of the shorter one are xor-ed with implicit zeros and stay as they are
+        for (left, right) in iter::zip(&mut symmetric_difference, &shorter) {
+            *left ^= right;
+        }
+
+        SimpleBitmap64 {
+            bits: symmetric_difference,
+        }
+    }
+}
+
+impl Sub for SimpleBitmap64 {
+    type Output = SimpleBitmap64;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+impl FromIterator<u32> for SimpleBitmap64 {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bitmap = SimpleBitmap64::new();
+        bitmap.extend(iter);
+
+        bitmap
+    }
+}
+
+impl Extend<u32> for SimpleBitmap64 {
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for index in iter {
+            self.set(index);
+        }
+    }
+}
+
+impl From<&SimpleBitmap> for SimpleBitmap64 {
+    fn from(bitmap: &SimpleBitmap) -> Self {
+        // two consecutive u32s form one u64, the lower u32 holding the lower indices
+        let bits = bitmap
+            .words()
+            .chunks(2)
+            .map(|pair| u64::from(pair[0]) | (u64::from(pair.get(1).copied().unwrap_or(0)) << 32))
+            .collect();
+
+        SimpleBitmap64 { bits }
+    }
+}
+
+impl From<&SimpleBitmap64> for SimpleBitmap {
+    fn from(bitmap: &SimpleBitmap64) -> Self {
+        let words = bitmap
+            .bits
+            .iter()
+            .flat_map(|bucket| [*bucket as u32, (bucket >> 32) as u32])
+            .collect();
+
+        SimpleBitmap::from_words(words)
+    }
+}
+
+impl PartialEq for SimpleBitmap64 {
+    fn eq(&self, other: &Self) -> bool {
+        // bitmaps with the same values are equal, no matter how many trailing zero u64s they carry
+        self.trimmed_bits() == other.trimmed_bits()
+    }
+}
+
+impl Eq for SimpleBitmap64 {}
+
+impl Hash for SimpleBitmap64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // trailing zero u64s are skipped, so that equal bitmaps always produce equal hashes
+        self.trimmed_bits().hash(state);
+    }
+}
+
+impl Debug for SimpleBitmap64 {
+    /// Shows summary statistics like [SimpleBitmap]. The alternate form `{:#?}` additionally dumps all u64s in binary.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let max = self.trimmed_bits().last().map(|bucket| {
+            (self.trimmed_bits().len() as u32 - 1) * 64 + (63 - bucket.leading_zeros())
+        });
+
+        let alternate = f.alternate();
+        let mut debug = f.debug_struct("SimpleBitmap64");
+        debug
+            .field("count", &self.count_ones())
+            .field("min", &self.iter().next())
+            .field("max", &max)
+            .field("words", &self.bits.len());
+
+        if alternate {
+            debug.field("bits", &BinaryWords64(&self.bits));
+        }
+
+        debug.finish()
+    }
+}
+
+/// Lists u64s in binary, from the lowest to the highest bit position.
+struct BinaryWords64<'a>(&'a [u64]);
+
+impl Debug for BinaryWords64<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        for bucket in self.0 {
+            list.entry(&format_args!("{bucket:064b}"));
+        }
+
+        list.finish()
+    }
+}
+
+/// Iterator over the indices of the values present in a [SimpleBitmap64], in ascending order.
+///
+/// Created by [SimpleBitmap64::iter].
+#[derive(Clone)]
+pub struct Ones64<'a> {
+    buckets: iter::Enumerate<slice::Iter<'a, u64>>,
+    bucket: u64,
+    bucket_base: u32,
+}
+
+impl Iterator for Ones64<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip u64s without any bit set instead of probing every single index
+        while self.bucket == 0 {
+            let (u64_index_in_bits_vec, bucket) = self.buckets.next()?;
+            self.bucket = *bucket;
+            self.bucket_base = u64_index_in_bits_vec as u32 * 64;
+        }
+
+        let bit_index_in_u64 = self.bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.bucket &= self.bucket - 1;
+
+        Some(self.bucket_base + bit_index_in_u64)
+    }
+}
+
+impl<'a> IntoIterator for &'a SimpleBitmap64 {
+    type Item = u32;
+    type IntoIter = Ones64<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_combines_like_simple_bitmaps;
+
+    #[test]
+    fn it_sets_and_gets_bits() {
+        // given
+        let mut bm = SimpleBitmap64::new();
+
+        // when
+        bm.set(63);
+        bm.set(64);
+        bm.set(1000);
+        bm.clear(1000);
+
+        // then
+        assert!(!bm.get(0));
+        assert!(bm.get(63));
+        assert!(bm.get(64));
+        assert!(!bm.get(1000));
+        assert_eq!(bm.count_ones(), 2);
+        assert_eq!(bm.bits.len(), 16);
+        assert_eq!(bm.iter().collect::<Vec<u32>>(), vec![63, 64]);
+    }
+
+    #[test]
+    fn it_combines_bitmaps_like_simple_bitmaps() {
+        // given
+        let left: Vec<u32> = (0..10_000).filter(|index| index % 3 == 0).collect();
+        let right: Vec<u32> = (5_000..30_000).filter(|index| index % 5 == 0).collect();
+
+        // then
+        assert_combines_like_simple_bitmaps::<SimpleBitmap64>(&left, &right);
+    }
+
+    #[test]
+    fn it_converts_from_and_to_simple_bitmaps() {
+        // given
+        let simple: SimpleBitmap = [0, 31, 32, 63, 64, 95].into_iter().collect();
+
+        // when
+        let wide = SimpleBitmap64::from(&simple);
+
+        // then
+        assert!(wide.iter().eq(&simple));
+        assert_eq!(SimpleBitmap::from(&wide), simple);
+    }
+
+    #[test]
+    fn it_summarizes_bitmaps_in_debug_output() {
+        // given
+        let bm: SimpleBitmap64 = [1, 70].into_iter().collect();
+
+        // then
+        assert_eq!(
+            format!("{bm:?}"),
+            "SimpleBitmap64 { count: 2, min: Some(1), max: Some(70), words: 2 }"
+        );
+        assert!(format!("{bm:#?}").contains(&format!("{:064b}", 0b1000000u64)));
+        assert!(!format!("{bm:?}").contains("bits"));
+    }
+}
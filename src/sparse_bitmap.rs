@@ -0,0 +1,370 @@
+use crate::Bitmap;
+use alloc::collections::BTreeMap;
+use alloc::collections::btree_map;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// Sparse implementation of a [Bitmap] that only stores u32s with at least one bit set.
+///
+/// The u32s are kept in a [BTreeMap] keyed by their index in the uncompressed bits vector, so memory grows with the
+/// number of non-zero u32s instead of with the highest index.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SparseBitmap {
+    // zero u32s are never stored
+    bits: BTreeMap<u32, u32>,
+}
+
+impl SparseBitmap {
+    pub fn new() -> Self {
+        Self {
+            bits: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if no value is present.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Returns the lowest index at which a value is present.
+    pub fn first_set(&self) -> Option<u32> {
+        self.bits
+            .first_key_value()
+            .map(|(u32_index, bucket)| u32_index * 32 + bucket.trailing_zeros())
+    }
+
+    /// Returns the highest index at which a value is present.
+    pub fn last_set(&self) -> Option<u32> {
+        self.bits
+            .last_key_value()
+            .map(|(u32_index, bucket)| u32_index * 32 + (31 - bucket.leading_zeros()))
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> SparseOnes<'_> {
+        SparseOnes {
+            buckets: self.bits.iter(),
+            bucket: 0,
+            bucket_base: 0,
+        }
+    }
+}
+
+impl SparseBitmap {
+    /// Returns the intersection of all given bitmaps, or an empty bitmap if none are given.
+    ///
+    /// Instead of visiting every stored u32, the bitmaps take turns seeking to the next u32 index that might be present
+    /// in all of them, starting with the bitmap of the fewest u32s, so long stretches without common u32s are skipped.
+    pub fn intersect_all(bitmaps: &[&SparseBitmap]) -> SparseBitmap {
+        let mut by_len = bitmaps.to_vec();
+        by_len.sort_unstable_by_key(|bitmap| bitmap.bits.len());
+
+        let mut intersection = BTreeMap::new();
+        if by_len.is_empty() {
+            return SparseBitmap { bits: intersection };
+        }
+
+        let mut candidate = 0;
+        'search: loop {
+            let mut bucket = u32::MAX;
+            for bitmap in &by_len {
+                let Some((&u32_index, &other_bucket)) = bitmap.bits.range(candidate..).next()
+                else {
+                    break 'search;
+                };
+
+                // a bitmap without the candidate moves the search to its next u32
+                if u32_index != candidate {
+                    candidate = u32_index;
+                    continue 'search;
+                }
+
+                bucket &= other_bucket;
+                if bucket == 0 {
+                    break;
+                }
+            }
+
+            if bucket != 0 {
+                intersection.insert(candidate, bucket);
+            }
+
+            match candidate.checked_add(1) {
+                Some(next) => candidate = next,
+                None => break,
+            }
+        }
+
+        SparseBitmap { bits: intersection }
+    }
+}
+
+impl Bitmap for SparseBitmap {
+    fn set(&mut self, index: u32) {
+        *self.bits.entry(index / 32).or_insert(0) |= 0b1 << (index & 0b11111);
+    }
+
+    fn clear(&mut self, index: u32) {
+        if let btree_map::Entry::Occupied(mut entry) = self.bits.entry(index / 32) {
+            *entry.get_mut() &= !(0b1 << (index & 0b11111));
+
+            // zero u32s are never stored
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bits
+            .get(&(index / 32))
+            .is_some_and(|bucket| (bucket >> (index & 0b11111)) & 0b1 == 1)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bits
+            .values()
+            .map(|bucket| u64::from(bucket.count_ones()))
+            .sum()
+    }
+}
+
+impl BitOr for SparseBitmap {
+    type Output = SparseBitmap;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        // the smaller map is merged into the larger one
+        let (mut union, smaller) = if self.bits.len() >= rhs.bits.len() {
+            (self.bits, rhs.bits)
+        } else {
+            (rhs.bits, self.bits)
+        };
+
+        for (u32_index, bucket) in smaller {
+            *union.entry(u32_index).or_insert(0) |= bucket;
+        }
+
+        SparseBitmap { bits: union }
+    }
+}
+
+impl BitAnd for SparseBitmap {
+    type Output = SparseBitmap;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        // only the u32s of the smaller map have to be looked up in the larger one
+        let (larger, smaller) = if self.bits.len() >= rhs.bits.len() {
+            (self.bits, rhs.bits)
+        } else {
+            (rhs.bits, self.bits)
+        };
+
+        let intersection = smaller
+            .into_iter()
+            .filter_map(|(u32_index, bucket)| {
+                let intersected = bucket & larger.get(&u32_index)?;
+                (intersected != 0).then_some((u32_index, intersected))
+            })
+            .collect();
+
+        SparseBitmap { bits: intersection }
+    }
+}
+
+impl BitXor for SparseBitmap {
+    type Output = SparseBitmap;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let mut symmetric_difference = self.bits;
+
+        for (u32_index, bucket) in rhs.bits {
+            let xored = symmetric_difference.get(&u32_index).copied().unwrap_or(0) ^ bucket;
+
+            if xored == 0 {
+                symmetric_difference.remove(&u32_index);
+            } else {
+                symmetric_difference.insert(u32_index, xored);
+            }
+        }
+
+        SparseBitmap {
+            bits: symmetric_difference,
+        }
+    }
+}
+
+impl Sub for SparseBitmap {
+    type Output = SparseBitmap;
+
+    /// Returns the bits set in `self` but not in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut difference = self.bits;
+
+        for (u32_index, bucket) in rhs.bits {
+            if let btree_map::Entry::Occupied(mut entry) = difference.entry(u32_index) {
+                *entry.get_mut() &= !bucket;
+
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+        }
+
+        SparseBitmap { bits: difference }
+    }
+}
+
+impl FromIterator<u32> for SparseBitmap {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut bitmap = SparseBitmap::new();
+        bitmap.extend(iter);
+
+        bitmap
+    }
+}
+
+impl Extend<u32> for SparseBitmap {
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for index in iter {
+            self.set(index);
+        }
+    }
+}
+
+/// Iterator over the indices of the values present in a [SparseBitmap], in ascending order.
+///
+/// Created by [SparseBitmap::iter].
+#[derive(Clone)]
+pub struct SparseOnes<'a> {
+    buckets: btree_map::Iter<'a, u32, u32>,
+    bucket: u32,
+    bucket_base: u32,
+}
+
+impl Iterator for SparseOnes<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket == 0 {
+            let (u32_index, bucket) = self.buckets.next()?;
+            self.bucket = *bucket;
+            self.bucket_base = u32_index * 32;
+        }
+
+        let bit_index_in_u32 = self.bucket.trailing_zeros();
+
+        // unset the lowest set bit, so the next call yields the following one
+        self.bucket &= self.bucket - 1;
+
+        Some(self.bucket_base + bit_index_in_u32)
+    }
+}
+
+impl<'a> IntoIterator for &'a SparseBitmap {
+    type Item = u32;
+    type IntoIter = SparseOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_combines_like_simple_bitmaps;
+
+    #[test]
+    fn it_sets_and_gets_high_scattered_bits() {
+        // given
+        let mut bm = SparseBitmap::new();
+
+        // when
+        bm.set(4_000_000_000);
+        bm.set(u32::MAX);
+        bm.set(3);
+        bm.clear(3);
+
+        // then
+        assert!(bm.get(4_000_000_000));
+        assert!(bm.get(u32::MAX));
+        assert!(!bm.get(3));
+        assert_eq!(bm.bits.len(), 2);
+        assert_eq!(bm.count_ones(), 2);
+        assert_eq!(bm.first_set(), Some(4_000_000_000));
+        assert_eq!(bm.last_set(), Some(u32::MAX));
+        assert_eq!(
+            bm.iter().collect::<Vec<u32>>(),
+            vec![4_000_000_000, u32::MAX]
+        );
+    }
+
+    #[test]
+    fn it_combines_bitmaps_like_simple_bitmaps() {
+        // given
+        let left: Vec<u32> = (0..10_000).filter(|index| index % 3 == 0).collect();
+        let right: Vec<u32> = (5_000..30_000).filter(|index| index % 5 == 0).collect();
+
+        // then
+        assert_combines_like_simple_bitmaps::<SparseBitmap>(&left, &right);
+        let sparse: SparseBitmap = left.iter().copied().collect();
+        assert_eq!(sparse.clone() - sparse, SparseBitmap::new());
+    }
+
+    #[test]
+    fn it_intersects_all_bitmaps_at_once() {
+        // given
+        let dense: SparseBitmap = (0..100_000).step_by(2).collect();
+        let scattered: SparseBitmap = [4, 5, 64, 99_998, u32::MAX].into_iter().collect();
+        let tail: SparseBitmap = (60..u32::MAX).step_by(1 << 20).chain([99_998]).collect();
+
+        // when
+        let intersection = SparseBitmap::intersect_all(&[&dense, &scattered, &tail]);
+
+        // then
+        assert_eq!(intersection.iter().collect::<Vec<u32>>(), vec![99_998]);
+        assert_eq!(
+            SparseBitmap::intersect_all(&[&scattered, &scattered]),
+            scattered
+        );
+        assert_eq!(SparseBitmap::intersect_all(&[]), SparseBitmap::new());
+    }
+
+    #[test]
+    fn it_provides_the_set_api_of_the_bitmap_trait() {
+        // given
+        let mut left: SparseBitmap = [1, 6, 40, 3_000_000].into_iter().collect();
+        let right: SparseBitmap = [6, 40, 70].into_iter().collect();
+        let subset: SparseBitmap = [6, 40].into_iter().collect();
+
+        // then
+        assert!(subset.is_subset(&left));
+        assert!(left.is_superset(&subset));
+        assert!(!left.is_disjoint(&right));
+        assert_eq!(left.intersection_len(&right), 2);
+        assert_eq!(left.union_len(&right), 5);
+        assert_eq!(left.difference_len(&right), 2);
+
+        // when
+        left.union_with(&right);
+        let union = left.clone();
+        left.intersect_with(&subset);
+        let intersection = left.clone();
+        left.union_with(&union);
+        left.difference_with(&subset);
+
+        // then
+        assert_eq!(
+            union.iter().collect::<Vec<u32>>(),
+            vec![1, 6, 40, 70, 3_000_000]
+        );
+        assert_eq!(intersection, subset);
+        assert_eq!(left.iter().collect::<Vec<u32>>(), vec![1, 70, 3_000_000]);
+        assert!(left.insert(6));
+        assert!(!left.insert(6));
+        assert!(left.remove(6));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::simple_bitmap::range_masks;
+use crate::{Bitmap, Ones, SimpleBitmap};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+/// Wrapper around a [SimpleBitmap] that records the indices of the u32s modified since the last call to
+/// [TrackedBitmap::take_dirty], so that only the modified parts of a large bitmap have to be written back.
+///
+/// Bitmaps with the same values are equal, no matter which changes they recorded.
+#[derive(Clone, Debug)]
+pub struct TrackedBitmap {
+    bitmap: SimpleBitmap,
+    // indices of the u32s in the bits vec whose bits changed
+    dirty: BTreeSet<usize>,
+}
+
+impl TrackedBitmap {
+    pub fn new() -> Self {
+        Self::from(SimpleBitmap::new())
+    }
+
+    /// Returns the wrapped bitmap.
+    pub fn bitmap(&self) -> &SimpleBitmap {
+        &self.bitmap
+    }
+
+    /// Unwraps the bitmap, dropping the recorded changes.
+    pub fn into_inner(self) -> SimpleBitmap {
+        self.bitmap
+    }
+
+    /// Returns `true` if any u32 was modified since the last call to [TrackedBitmap::take_dirty].
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Returns the indices of the u32s modified since the last call, in ascending order, and starts recording anew.
+    /// Bit `i` of the u32 at index `j` holds the value `j * 32 + i`.
+    pub fn take_dirty(&mut self) -> Vec<usize> {
+        mem::take(&mut self.dirty).into_iter().collect()
+    }
+
+    /// Sets the presence of all values in the given range.
+    pub fn set_range(&mut self, range: Range<u32>) {
+        // a u32 changes unless all bits of the range are already set
+        self.mark_range(&range, |bucket, mask| bucket & mask != mask);
+        self.bitmap.set_range(range);
+    }
+
+    /// Clears the presence of all values in the given range.
+    pub fn clear_range(&mut self, range: Range<u32>) {
+        // a u32 changes if any bit of the range is set
+        self.mark_range(&range, |bucket, mask| bucket & mask != 0);
+        self.bitmap.clear_range(range);
+    }
+
+    /// Returns an iterator over the indices of the present values, in ascending order.
+    pub fn iter(&self) -> Ones<'_> {
+        self.bitmap.iter()
+    }
+
+    /// Marks the u32s touched by the range for which `changes` tells that the modification changes them, given the u32
+    /// before the modification and the mask of its bits within the range.
+    fn mark_range(&mut self, range: &Range<u32>, changes: impl Fn(u32, u32) -> bool) {
+        let words = self.bitmap.words();
+
+        for (u32_index_in_bits_vec, mask) in range_masks(range.clone()) {
+            // u32s beyond the end of the bits vec have all bits cleared
+            let bucket = words.get(u32_index_in_bits_vec).copied().unwrap_or(0);
+
+            if changes(bucket, mask) {
+                self.dirty.insert(u32_index_in_bits_vec);
+            }
+        }
+    }
+}
+
+impl From<SimpleBitmap> for TrackedBitmap {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        Self {
+            bitmap,
+            dirty: BTreeSet::new(),
+        }
+    }
+}
+
+impl PartialEq for TrackedBitmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitmap == other.bitmap
+    }
+}
+
+impl Eq for TrackedBitmap {}
+
+impl Bitmap for TrackedBitmap {
+    // only modifications that change a bit mark its u32 as dirty
+
+    fn set(&mut self, index: u32) {
+        if self.bitmap.insert(index) {
+            self.dirty.insert((index / 32) as usize);
+        }
+    }
+
+    fn clear(&mut self, index: u32) {
+        if self.bitmap.remove(index) {
+            self.dirty.insert((index / 32) as usize);
+        }
+    }
+
+    fn get(&self, index: u32) -> bool {
+        self.bitmap.get(index)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter()
+    }
+
+    fn count_ones(&self) -> u64 {
+        self.bitmap.count_ones()
+    }
+}
+
+impl FromIterator<u32> for TrackedBitmap {
+    /// Collects the values into a bitmap without any recorded changes.
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        TrackedBitmap::from(iter.into_iter().collect::<SimpleBitmap>())
+    }
+}
+
+impl<'a> IntoIterator for &'a TrackedBitmap {
+    type Item = u32;
+    type IntoIter = Ones<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_records_the_modified_u32s() {
+        // given
+        let mut bm: TrackedBitmap = [1, 6, 40].into_iter().collect();
+
+        // when
+        bm.set(6);
+        bm.clear(100);
+        bm.set(1_000);
+        bm.clear(40);
+        bm.set_range(64..130);
+
+        // then
+        assert!(bm.is_dirty());
+        assert_eq!(bm.take_dirty(), vec![1, 2, 3, 4, 31]);
+        assert!(!bm.is_dirty());
+        assert_eq!(bm.take_dirty(), Vec::<usize>::new());
+        assert!(bm.get(1_000));
+    }
+
+    #[test]
+    fn it_only_records_u32s_changed_by_ranges() {
+        // given
+        let mut bm = TrackedBitmap::new();
+        bm.set_range(0..64);
+        bm.take_dirty();
+
+        // when
+        bm.set_range(10..40);
+        bm.set_range(60..70);
+        bm.clear_range(100..200);
+        bm.clear_range(40..64);
+
+        // then
+        assert_eq!(bm.take_dirty(), vec![1, 2]);
+        assert_eq!(bm.count_ones(), 70 - 24);
+    }
+
+    #[test]
+    fn it_compares_the_values_only() {
+        // given
+        let mut dirty = TrackedBitmap::new();
+        dirty.set(3);
+        let clean: TrackedBitmap = [3].into_iter().collect();
+
+        // then
+        assert_eq!(dirty, clean);
+        assert_ne!(dirty, TrackedBitmap::new());
+    }
+}
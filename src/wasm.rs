@@ -0,0 +1,125 @@
+//! JavaScript bindings of [SimpleBitmap] via wasm-bindgen, so browser code can share the bitmap logic of the backend.
+
+use crate::{Bitmap, SimpleBitmap};
+use wasm_bindgen::prelude::*;
+
+/// Wraps a [SimpleBitmap] for JavaScript, where it is exported as the class `Bitmap`.
+#[wasm_bindgen(js_name = Bitmap)]
+pub struct WasmBitmap {
+    bitmap: SimpleBitmap,
+}
+
+#[wasm_bindgen(js_class = Bitmap)]
+impl WasmBitmap {
+    /// Creates an empty bitmap.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            bitmap: SimpleBitmap::new(),
+        }
+    }
+
+    /// Creates a bitmap in which the given values are present.
+    #[wasm_bindgen(js_name = fromValues)]
+    pub fn from_values(values: &[u32]) -> Self {
+        Self {
+            bitmap: SimpleBitmap::from(values),
+        }
+    }
+
+    /// Sets the presence of a value.
+    pub fn set(&mut self, index: u32) {
+        self.bitmap.set(index);
+    }
+
+    /// Clears the presence of a value.
+    pub fn clear(&mut self, index: u32) {
+        self.bitmap.clear(index);
+    }
+
+    /// Returns whether a value is present.
+    pub fn get(&self, index: u32) -> bool {
+        self.bitmap.get(index)
+    }
+
+    /// Counts the values that are present. Returned as a JavaScript number, which holds any count exactly.
+    pub fn count(&self) -> f64 {
+        self.bitmap.count_ones() as f64
+    }
+
+    /// Returns a new bitmap with the values present in either bitmap.
+    pub fn union(&self, other: &WasmBitmap) -> WasmBitmap {
+        let mut bitmap = self.bitmap.clone();
+        bitmap |= &other.bitmap;
+
+        WasmBitmap { bitmap }
+    }
+
+    /// Returns a new bitmap with the values present in both bitmaps.
+    pub fn intersection(&self, other: &WasmBitmap) -> WasmBitmap {
+        let mut bitmap = self.bitmap.clone();
+        bitmap &= &other.bitmap;
+
+        WasmBitmap { bitmap }
+    }
+
+    /// Returns the present values in ascending order, as a `Uint32Array`.
+    pub fn values(&self) -> Vec<u32> {
+        self.bitmap.iter().collect()
+    }
+
+    /// Serializes the bitmap into the binary format of [SimpleBitmap::to_bytes], as a `Uint8Array`.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bitmap.to_bytes()
+    }
+
+    /// Deserializes a bitmap written by `toBytes`. Throws an `Error` if the bytes are malformed.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmBitmap, JsError> {
+        let bitmap = SimpleBitmap::from_bytes(bytes)?;
+
+        Ok(WasmBitmap { bitmap })
+    }
+}
+
+impl From<SimpleBitmap> for WasmBitmap {
+    fn from(bitmap: SimpleBitmap) -> Self {
+        Self { bitmap }
+    }
+}
+
+impl From<WasmBitmap> for SimpleBitmap {
+    fn from(bitmap: WasmBitmap) -> Self {
+        bitmap.bitmap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // errors can only be tested on a wasm target, as creating a JsError calls into JavaScript
+    #[test]
+    fn it_wraps_a_simple_bitmap() {
+        // given
+        let mut left = WasmBitmap::from_values(&[1, 5]);
+        let right = WasmBitmap::from_values(&[5, 70]);
+
+        // when
+        left.set(3);
+        left.clear(1);
+
+        // then
+        assert!(left.get(3));
+        assert_eq!(left.count(), 2.0);
+        assert_eq!(left.union(&right).values(), vec![3, 5, 70]);
+        assert_eq!(left.intersection(&right).values(), vec![5]);
+        assert_eq!(
+            WasmBitmap::from_bytes(&left.to_bytes())
+                .ok()
+                .map(|bm| bm.values()),
+            Some(vec![3, 5])
+        );
+    }
+}